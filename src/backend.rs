@@ -0,0 +1,121 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Register access backends
+//!
+//! The registers access the memory through the backend they are created with. Registers created with ``new`` use
+//! [HardwareMmio], performing volatile reads and writes to the memory mapped I/O address. Passing a different
+//! backend to ``with_backend`` redirects the accesses of this register only. In contrast to the ``mock`` feature,
+//! replacing the whole address space for all registers, each test could use its own backend, so the tests could run
+//! in parallel without any feature flag.
+//!
+//! The [Memory] backend provides a small address space backed by ordinary memory to run driver code on the host.
+//!
+//! ```
+//! # use ruspiro_mmio_register::*;
+//! # use ruspiro_mmio_register::backend::Memory;
+//! fn main() {
+//!     let memory = Memory::<2>::new(0x3F20_0000);
+//!     let ctrl = ReadWrite::<u32, _>::with_backend(0x3F20_0004, &memory);
+//!     ctrl.set(0x1F);
+//!     assert_eq!(ctrl.get(), 0x1F);
+//!     assert_eq!(memory.peek::<u32>(0x3F20_0004), 0x1F);
+//! }
+//! ```
+//!
+//! # Hint
+//! ``AtomicReadWrite`` and ``WriteOnlyShadow`` always access the hardware as the atomic read-modify-write and the
+//! shadow copy can not be redirected to an arbitrary backend.
+
+use crate::RegisterType;
+use core::cell::UnsafeCell;
+
+/// The memory access of a register. The address is the one the register was created with.
+pub trait MmioAccess: Clone {
+    /// Read the raw value of the register at ``addr``.
+    fn read<T: RegisterType + Into<u64>>(&self, addr: usize) -> T;
+
+    /// Write the raw value to the register at ``addr``.
+    fn write<T: RegisterType + Into<u64>>(&self, addr: usize, value: T);
+}
+
+/// The default backend accessing the memory mapped I/O register with volatile reads and writes. With the ``mock``
+/// feature enabled the accesses go to the mocked address space instead.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HardwareMmio;
+
+impl MmioAccess for HardwareMmio {
+    #[inline(always)]
+    fn read<T: RegisterType + Into<u64>>(&self, addr: usize) -> T {
+        crate::mmio_read(addr as *const T)
+    }
+
+    #[inline(always)]
+    fn write<T: RegisterType + Into<u64>>(&self, addr: usize, value: T) {
+        crate::mmio_write(addr as *mut T, value)
+    }
+}
+
+/// An address space of ``N`` 64 bit words in ordinary memory starting at the ``base`` address. Registers use it
+/// by reference with ``with_backend(addr, &memory)``. All registers sharing the same memory see each others writes.
+///
+/// # Panics
+/// Accessing an address outside of the address space panics.
+#[derive(Debug)]
+pub struct Memory<const N: usize> {
+    base: usize,
+    words: UnsafeCell<[u64; N]>,
+}
+
+impl<const N: usize> Memory<N> {
+    /// Create the address space starting at ``base`` with all registers set to 0.
+    #[allow(dead_code)]
+    pub const fn new(base: usize) -> Self {
+        Self {
+            base,
+            words: UnsafeCell::new([0; N]),
+        }
+    }
+
+    /// Read the raw value stored at ``addr`` without going through a register.
+    #[inline]
+    #[allow(dead_code)]
+    pub fn peek<T: RegisterType>(&self, addr: usize) -> T {
+        // the access is within the words and the memory is not shared across threads
+        unsafe { self.location::<T>(addr).read_unaligned() }
+    }
+
+    /// Store the raw value at ``addr`` without going through a register, e.g. to set the state the hardware would
+    /// provide.
+    #[inline]
+    #[allow(dead_code)]
+    pub fn poke<T: RegisterType>(&self, addr: usize, value: T) {
+        // the access is within the words and the memory is not shared across threads
+        unsafe { self.location::<T>(addr).write_unaligned(value) }
+    }
+
+    fn location<T>(&self, addr: usize) -> *mut T {
+        let offset = addr.checked_sub(self.base).expect("address below the memory backend");
+        assert!(
+            offset + core::mem::size_of::<T>() <= N * 8,
+            "address beyond the memory backend"
+        );
+        unsafe { (self.words.get() as *mut u8).add(offset) as *mut T }
+    }
+}
+
+impl<const N: usize> MmioAccess for &Memory<N> {
+    #[inline]
+    fn read<T: RegisterType + Into<u64>>(&self, addr: usize) -> T {
+        self.peek(addr)
+    }
+
+    #[inline]
+    fn write<T: RegisterType + Into<u64>>(&self, addr: usize, value: T) {
+        self.poke(addr, value)
+    }
+}
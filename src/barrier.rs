@@ -0,0 +1,104 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Memory barriers
+//!
+//! The CPU may complete accesses to different peripherals out of order. On the Raspberry Pi the peripheral bus does
+//! not guarantee that the data read from one peripheral arrives in order with the data read from another one. A
+//! memory barrier is therefore required when switching from the accesses of one peripheral to those of another one.
+//!
+//! With the ``arm-barriers`` feature enabled the registers provide ``get_barrier`` and ``set_barrier`` performing the
+//! access with the barriers required around it. On ``aarch64`` and ARMv7 the barriers are ``dmb sy`` and ``dsb sy``.
+//! ARMv6, e.g. the BCM2835 of the Raspberry Pi 1 and Zero, does not know these instructions, so the equivalent CP15
+//! operations are used there. Other targets do not provide memory barriers for MMIO, the feature is only accepted
+//! there together with the ``mock`` feature for testing on the host, where a sequentially consistent fence is used.
+//!
+//! ```no_run
+//! # use ruspiro_mmio_register::*;
+//! define_mmio_register!(
+//!     UART_DR<ReadWrite<u32>@(0x3F20_1000)>,
+//!     GPIO_LEV<ReadOnly<u32>@(0x3F20_0034)>
+//! );
+//!
+//! fn main() {
+//!     UART_DR::Register.set_barrier(b'A' as u32);
+//!     let level = GPIO_LEV::Register.get_barrier();
+//!     // wait for all outstanding memory accesses to complete
+//!     barrier::data_sync_barrier();
+//! }
+//! ```
+//!
+//! A sequence of accesses to the same register, e.g. configuring a peripheral, does not need barriers around each of
+//! them. ``transaction`` performs the accesses of the closure given with a single barrier before and after them:
+//!
+//! ```no_run
+//! # use ruspiro_mmio_register::*;
+//! define_mmio_register!(
+//!     UART_CR<ReadWrite<u32>@(0x3F20_1030)> {
+//!         UARTEN OFFSET(0),
+//!         TXE OFFSET(8),
+//!         RXE OFFSET(9)
+//!     }
+//! );
+//!
+//! fn main() {
+//!     UART_CR::Register.transaction(|cr| {
+//!         cr.set(0);
+//!         cr.modify(UART_CR::TXE, 1);
+//!         cr.modify(UART_CR::RXE, 1);
+//!         cr.modify(UART_CR::UARTEN, 1);
+//!     });
+//! }
+//! ```
+
+#[cfg(not(any(target_arch = "aarch64", target_arch = "arm", feature = "mock")))]
+compile_error!("the arm-barriers feature requires an aarch64 or arm target or the mock feature");
+
+/// Data memory barrier. All memory accesses before the barrier are observed before any memory access after it.
+#[cfg(any(target_arch = "aarch64", all(target_arch = "arm", target_feature = "v7")))]
+#[inline]
+pub fn data_memory_barrier() {
+    unsafe { core::arch::asm!("dmb sy", options(nostack, preserves_flags)) }
+}
+
+/// Data synchronization barrier. No instruction after the barrier is executed before all memory accesses before it
+/// are completed.
+#[cfg(any(target_arch = "aarch64", all(target_arch = "arm", target_feature = "v7")))]
+#[inline]
+pub fn data_sync_barrier() {
+    unsafe { core::arch::asm!("dsb sy", options(nostack, preserves_flags)) }
+}
+
+/// Data memory barrier. On ARMv6 this is the CP15 operation the ``dmb`` instruction replaced with ARMv7.
+#[cfg(all(target_arch = "arm", not(target_feature = "v7")))]
+#[inline]
+pub fn data_memory_barrier() {
+    unsafe { core::arch::asm!("mcr p15, 0, {0}, c7, c10, 5", in(reg) 0, options(nostack, preserves_flags)) }
+}
+
+/// Data synchronization barrier. On ARMv6 this is the CP15 operation the ``dsb`` instruction replaced with ARMv7.
+#[cfg(all(target_arch = "arm", not(target_feature = "v7")))]
+#[inline]
+pub fn data_sync_barrier() {
+    unsafe { core::arch::asm!("mcr p15, 0, {0}, c7, c10, 4", in(reg) 0, options(nostack, preserves_flags)) }
+}
+
+/// Data memory barrier. Without memory barriers on the host this is a sequentially consistent fence ordering the
+/// accesses to the mocked address space.
+#[cfg(all(not(any(target_arch = "aarch64", target_arch = "arm")), feature = "mock"))]
+#[inline]
+pub fn data_memory_barrier() {
+    core::sync::atomic::fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+/// Data synchronization barrier. Without memory barriers on the host this is a sequentially consistent fence
+/// ordering the accesses to the mocked address space.
+#[cfg(all(not(any(target_arch = "aarch64", target_arch = "arm")), feature = "mock"))]
+#[inline]
+pub fn data_sync_barrier() {
+    core::sync::atomic::fence(core::sync::atomic::Ordering::SeqCst);
+}
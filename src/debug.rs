@@ -0,0 +1,164 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Debug output helper
+//!
+//! Helper used by the code generated with the register definition macros to format the decoded register contents.
+//!
+
+use core::fmt;
+
+/// The notation the raw value of a register field is printed in. The notation of a field is given with the
+/// ``#[fmt(hex)]`` or ``#[fmt(bin)]`` attribute of the field. Otherwise fields of up to 4 bits are printed in binary
+/// and wider fields in hexadecimal notation with upper case digits, like ``0x1F``.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Radix {
+    Binary,
+    Hex,
+}
+
+impl Radix {
+    /// The notation given for a field or the default notation of a field of ``bits`` bits
+    pub const fn of_field(hint: Option<Radix>, bits: u32) -> Self {
+        match hint {
+            Some(radix) => radix,
+            None if bits > 4 => Radix::Hex,
+            None => Radix::Binary,
+        }
+    }
+}
+
+/// The decoded value of a register field. If the field defines specific values and the current value matches one of
+/// them, the name of this value is printed. Otherwise the raw field value is printed in the notation given.
+pub struct FieldDebug<T: 'static> {
+    value: T,
+    variants: &'static [(&'static str, T)],
+    radix: Radix,
+}
+
+impl<T> FieldDebug<T> {
+    pub fn new(value: T, variants: &'static [(&'static str, T)], radix: Radix) -> Self {
+        Self { value, variants, radix }
+    }
+}
+
+impl<T: PartialEq + fmt::Binary + fmt::UpperHex> fmt::Debug for FieldDebug<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.variants.iter().find(|(_, value)| *value == self.value) {
+            Some((name, _)) => f.write_str(name),
+            None => match self.radix {
+                Radix::Binary => write!(f, "{:#b}", self.value),
+                Radix::Hex => write!(f, "{:#X}", self.value),
+            },
+        }
+    }
+}
+
+/// The decoded values of a register field that differ between two snapshots of the register.
+pub struct FieldDiff<T: 'static> {
+    old: FieldDebug<T>,
+    new: FieldDebug<T>,
+}
+
+impl<T> FieldDiff<T> {
+    pub fn new(old: T, new: T, variants: &'static [(&'static str, T)], radix: Radix) -> Self {
+        Self {
+            old: FieldDebug::new(old, variants, radix),
+            new: FieldDebug::new(new, variants, radix),
+        }
+    }
+}
+
+impl<T: PartialEq + fmt::Binary + fmt::UpperHex> fmt::Debug for FieldDiff<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} -> {:?}", self.old, self.new)
+    }
+}
+
+/// A register of a peripheral printed with the peripheral dump. Write only registers could not be read and are
+/// printed as such.
+pub struct RegisterDump {
+    pub name: &'static str,
+    pub dump: Option<fn(&mut fmt::Formatter<'_>) -> fmt::Result>,
+}
+
+impl fmt::Debug for RegisterDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.dump {
+            Some(dump) => dump(f),
+            None => f.write_str("<write only>"),
+        }
+    }
+}
+
+/// The registers of a peripheral printed with their decoded contents. Each register is read when printed.
+pub struct PeripheralDump {
+    name: &'static str,
+    registers: &'static [RegisterDump],
+}
+
+impl PeripheralDump {
+    pub fn new(name: &'static str, registers: &'static [RegisterDump]) -> Self {
+        Self { name, registers }
+    }
+}
+
+impl fmt::Debug for PeripheralDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct(self.name);
+        for register in self.registers {
+            debug.field(register.name, register);
+        }
+        debug.finish()
+    }
+}
+
+/// A register field printed with the ``Display`` of a register snapshot. If the field defines specific values and the
+/// current value matches one of them, the name of this value is printed. Otherwise the raw field value is printed in
+/// the notation of the field like with ``Debug``.
+pub struct FieldDisplay<T: 'static> {
+    name: &'static str,
+    value: T,
+    variants: &'static [(&'static str, T)],
+    radix: Radix,
+}
+
+impl<T> FieldDisplay<T> {
+    pub fn new(name: &'static str, value: T, variants: &'static [(&'static str, T)], radix: Radix) -> Self {
+        Self {
+            name,
+            value,
+            variants,
+            radix,
+        }
+    }
+}
+
+/// Print the register contents in a single line with the name of the register followed by the decoded fields as
+/// ``FIELD=VALUE``. The fields compiled out by their ``cfg`` attributes are given as ``None``.
+pub fn display_values<T: PartialEq + fmt::Binary + fmt::UpperHex>(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    raw: T,
+    fields: &[Option<FieldDisplay<T>>],
+) -> fmt::Result {
+    if fields.iter().all(Option::is_none) {
+        return write!(f, "{}({:#X})", name, raw);
+    }
+    write!(f, "{} {{", name)?;
+    for (index, field) in fields.iter().flatten().enumerate() {
+        if index > 0 {
+            f.write_str(",")?;
+        }
+        match (field.variants.iter().find(|(_, value)| *value == field.value), field.radix) {
+            (Some((variant, _)), _) => write!(f, " {}={}", field.name, variant)?,
+            (None, Radix::Binary) => write!(f, " {}={:#b}", field.name, field.value)?,
+            (None, Radix::Hex) => write!(f, " {}={:#X}", field.name, field.value)?,
+        }
+    }
+    f.write_str(" }")
+}
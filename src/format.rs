@@ -0,0 +1,98 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # defmt output
+//!
+//! With the ``defmt`` feature enabled the register snapshots returned by ``read_all`` implement [defmt::Format]. They
+//! are printed with the name of the register and the decoded value of each field, the same way as their debug output.
+//!
+//! As [RegisterFieldValue] is defined in the ``ruspiro-register`` crate it can not implement [defmt::Format] here.
+//! Wrap it into a [FieldValue] to print it.
+//!
+//! ```no_run
+//! # use ruspiro_mmio_register::*;
+//! define_mmio_register!(
+//!     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+//!         BAR OFFSET(0),
+//!         BAZ OFFSET(1) BITS(3)
+//!     }
+//! );
+//!
+//! fn main() {
+//!     defmt::info!("{}", FOO::Register.read_all());
+//!     defmt::info!("{}", format::FieldValue(FOO::Register.read_value(FOO::BAZ)));
+//! }
+//! ```
+
+use crate::debug::Radix;
+use crate::RegisterFieldValue;
+use crate::RegisterType;
+use defmt::{Format, Formatter};
+
+/// Wrapper of a [RegisterFieldValue] implementing [defmt::Format]. It prints the raw value and the mask of the field.
+pub struct FieldValue<T: RegisterType>(pub RegisterFieldValue<T>);
+
+macro_rules! fieldvalue_impl {
+    ($($t:ty),*) => {$(
+        impl Format for FieldValue<$t> {
+            fn format(&self, f: Formatter<'_>) {
+                defmt::write!(f, "{=?:#b} (mask {=?:#b})", self.0.raw_value(), self.0.mask());
+            }
+        }
+    )*};
+}
+
+fieldvalue_impl![u8, u16, u32, u64];
+
+/// The decoded value of a register field as printed with defmt. If the field defines specific values and the current
+/// value matches one of them, the name of this value is printed. Otherwise the raw field value is printed in the
+/// notation given, like the debug output.
+#[doc(hidden)]
+pub struct FieldFormat<T: 'static> {
+    name: &'static str,
+    value: T,
+    variants: &'static [(&'static str, T)],
+    radix: Radix,
+}
+
+impl<T> FieldFormat<T> {
+    pub fn new(name: &'static str, value: T, variants: &'static [(&'static str, T)], radix: Radix) -> Self {
+        Self {
+            name,
+            value,
+            variants,
+            radix,
+        }
+    }
+}
+
+/// Print the register contents with the name of the register followed by the decoded fields. The fields compiled
+/// out by their ``cfg`` attributes are given as ``None``.
+#[doc(hidden)]
+pub fn format_values<T: PartialEq + Format>(
+    f: Formatter<'_>,
+    name: &str,
+    raw: T,
+    fields: &[Option<FieldFormat<T>>],
+) {
+    if fields.iter().all(Option::is_none) {
+        defmt::write!(f, "{=str}({=?:#X})", name, raw);
+        return;
+    }
+    defmt::write!(f, "{=str} {{", name);
+    for (index, field) in fields.iter().flatten().enumerate() {
+        if index > 0 {
+            defmt::write!(f, ",");
+        }
+        match (field.variants.iter().find(|(_, value)| *value == field.value), field.radix) {
+            (Some((variant, _)), _) => defmt::write!(f, " {=str}: {=str}", field.name, variant),
+            (None, Radix::Binary) => defmt::write!(f, " {=str}: {=?:#b}", field.name, field.value),
+            (None, Radix::Hex) => defmt::write!(f, " {=str}: {=?:#X}", field.name, field.value),
+        }
+    }
+    defmt::write!(f, " }}");
+}
@@ -0,0 +1,89 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Waiting for register fields asynchronously
+//!
+//! With the ``async`` feature enabled the readable registers provide ``wait_field`` returning a future that resolves
+//! once the register field contains the value given. Each poll of the future reads the register once. If the field
+//! does not match yet, the future is pending and the task is scheduled to be polled again with the [Repoll] strategy.
+//!
+//! As there is no executor or timer this crate could rely on, the strategy is provided by the user, e.g. to wake the
+//! task from a timer interrupt. Without such a waker source ``wait_field`` uses [Yield], that wakes the task right
+//! away. The wait then degrades to a polling loop that yields to the other tasks of the executor between the reads.
+//!
+//! ```no_run
+//! # use ruspiro_mmio_register::*;
+//! # use core::task::Waker;
+//! define_mmio_register!(
+//!     STATUS<ReadOnly<u32>@(0x3F20_0000)> {
+//!         READY OFFSET(0)
+//!     }
+//! );
+//!
+//! struct Timer;
+//!
+//! impl future::Repoll for Timer {
+//!     fn schedule(&self, waker: &Waker) {
+//!         // arm a timer that calls waker.wake_by_ref() when it fires
+//!     }
+//! }
+//!
+//! async fn wait_ready() {
+//!     STATUS::Register.wait_field(STATUS::READY, 1).await;
+//!     STATUS::Register.wait_field_with(STATUS::READY, 1, Timer).await;
+//! }
+//! ```
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+/// Strategy to get a pending wait for a register field polled again.
+pub trait Repoll {
+    /// Arrange that the task of the ``waker`` is woken once the register shall be read again.
+    fn schedule(&self, waker: &Waker);
+}
+
+/// Wake the task right away to read the register again with the next poll of the executor.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Yield;
+
+impl Repoll for Yield {
+    fn schedule(&self, waker: &Waker) {
+        waker.wake_by_ref();
+    }
+}
+
+/// Future that resolves once the condition is met. The condition is checked once with each poll.
+pub struct WaitUntil<F, P> {
+    condition: F,
+    repoll: P,
+}
+
+impl<F: FnMut() -> bool, P: Repoll> WaitUntil<F, P> {
+    /// Create the future checking the ``condition`` and scheduling the next poll with ``repoll``
+    pub fn new(condition: F, repoll: P) -> Self {
+        Self { condition, repoll }
+    }
+}
+
+// the future is never pinned structurally
+impl<F, P> Unpin for WaitUntil<F, P> {}
+
+impl<F: FnMut() -> bool, P: Repoll> Future for WaitUntil<F, P> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        if (this.condition)() {
+            Poll::Ready(())
+        } else {
+            this.repoll.schedule(cx.waker());
+            Poll::Pending
+        }
+    }
+}
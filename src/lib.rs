@@ -38,6 +38,9 @@
 //!     // combine two field values with logical OR
 //!     FOO::Register.write_value( FOO::BAL::VAL1 | FOO::BAL::VAL2 );
 //! 
+//!     // update a field value while keeping the contents of all other fields
+//!     FOO::Register.update_value( FOO::BAL::VAL2 );
+//! 
 //!     // if there is no field defined for the MMIO register or raw value storage
 //!     // is preffered the raw value could be written
 //!     FOO::Register.write_value(FOO::BAZ::with_value(0b101));
@@ -47,106 +50,2253 @@
 //!     // reading from the MMIO register works in a simmilar way
 //!     let baz_val = FOO::Register.read(FOO::BAL); // return 0b01 or 0b10 eg.
 //!     let baz_field = FOO::Register.read_value(FOO::BAL); // returns a FieldValue
+//!     let baz_wide: u64 = FOO::Register.read_field_into(FOO::BAZ); // converted with From
 //!     let raw_val = FOO::Register.get();
 //! }
 //! ```
 //!
 
+#[cfg(feature = "mock")]
+extern crate std;
+
+#[cfg(not(feature = "mock"))]
 use core::ptr::{read_volatile, write_volatile};
+use core::cell::Cell;
+use core::marker::PhantomData;
+use core::ops::{Deref, RangeInclusive};
+use core::ptr::NonNull;
+use core::sync::atomic::{compiler_fence, AtomicBool, AtomicUsize, Ordering};
+
+pub use ruspiro_register::*;
+pub mod macros;
+
+pub mod backend;
+use backend::{HardwareMmio, MmioAccess};
+
+#[doc(hidden)]
+pub mod debug;
+
+#[doc(hidden)]
+pub use paste;
+
+#[cfg(feature = "mock")]
+pub mod mock;
+
+#[cfg(feature = "trace")]
+pub mod trace;
+
+#[cfg(feature = "async")]
+pub mod future;
+
+#[cfg(feature = "arm-barriers")]
+pub mod barrier;
+
+#[cfg(feature = "defmt")]
+pub mod format;
+
+#[cfg(feature = "defmt")]
+#[doc(hidden)]
+pub use defmt;
+
+/// This struct allows read only access to a register.
+#[derive(Clone, Copy, Debug)]
+pub struct ReadOnly<T: RegisterType, B = HardwareMmio> {
+    ptr: *const T, // base address for the register, that is never written
+    backend: B, // the memory access of the register
+}
+
+/// This struct allows write only access to a register.
+#[derive(Clone, Copy, Debug)]
+pub struct WriteOnly<T: RegisterType, B = HardwareMmio> {
+    ptr: *mut T, // base address for the register
+    backend: B, // the memory access of the register
+}
+
+/// This struct allows read/write access to a register.
+///
+/// The register access structs only contain the address of the register and its backend, so they are ``Copy``. A
+/// register could be passed by value and still be used afterwards.
+///
+/// # Example
+/// ```
+/// # use ruspiro_mmio_register::*;
+/// # use ruspiro_mmio_register::backend::Memory;
+/// const CTRL: ReadWrite<u32> = ReadWrite::<u32>::new(0x3F20_1030);
+///
+/// fn enable(register: ReadWrite<u32, &Memory<1>>) {
+///     register.modify(RegisterField::<u32>::new(1, 0), 1);
+/// }
+///
+/// fn main() {
+///     let ctrl = CTRL;
+///     assert_eq!(ctrl.address(), CTRL.address());
+///
+///     let memory = Memory::<1>::new(0x1000);
+///     let register = ReadWrite::<u32, _>::with_backend(0x1000, &memory);
+///     enable(register);
+///     // the register has been copied into the function and is still usable
+///     assert_eq!(register.get(), 1);
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ReadWrite<T: RegisterType, B = HardwareMmio> {
+    ptr: *mut T, // base address for the register
+    backend: B, // the memory access of the register
+}
+
+/// This struct allows read only access to a register that stores its value in big-endian byte order. The value read
+/// is converted into the host endianness, so the register fields are defined as stated in the datasheet.
+#[derive(Clone, Copy, Debug)]
+pub struct ReadOnlyBE<T: RegisterType, B = HardwareMmio> {
+    ptr: *const T, // base address for the register, that is never written
+    backend: B, // the memory access of the register
+}
+
+/// This struct allows write only access to a register that stores its value in big-endian byte order. The value
+/// written is converted from the host endianness, so the register fields are defined as stated in the datasheet.
+#[derive(Clone, Copy, Debug)]
+pub struct WriteOnlyBE<T: RegisterType, B = HardwareMmio> {
+    ptr: *mut T, // base address for the register
+    backend: B, // the memory access of the register
+}
+
+/// This struct allows read/write access to a register that stores its value in big-endian byte order. All field
+/// operations like ``read``, ``write`` or ``modify`` work on the value in host endianness, so the register fields
+/// are defined as stated in the datasheet.
+///
+/// # Example
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWriteBE<u32>@(0x3F20_0000)> {
+///         LOW OFFSET(0) BITS(8)
+///     }
+/// );
+///
+/// fn main() {
+///     FOO::Register.set(0x12);
+///     // the value is stored as 0x1200_0000 in the register memory but reads back in host endianness
+///     assert_eq!(FOO::Register.read(FOO::LOW), 0x12);
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ReadWriteBE<T: RegisterType, B = HardwareMmio> {
+    ptr: *mut T, // base address for the register
+    backend: B, // the memory access of the register
+}
+
+/// This struct allows read/write access to a register where the read-modify-write of the ``modify`` functions is
+/// performed as a single atomic operation. This prevents two cores updating different fields of the same register
+/// from overwriting each others changes.
+///
+/// # Hint
+/// This only works where the hardware actually supports atomic accesses to the memory mapped I/O region. The atomic
+/// update is a compare-and-swap loop of exclusive loads and stores, e.g. ``ldxr``/``stxr`` on ``aarch64``. These
+/// require the MMU to be configured and the exclusive monitor of the system to cover the memory region of the
+/// register. Whether it does for device memory is implementation defined, so check the datasheet of the SoC. Using
+/// this on memory that does not support exclusive accesses may lead to a data abort or a never ending ``modify``.
+#[derive(Clone, Copy, Debug)]
+pub struct AtomicReadWrite<T: RegisterType> {
+    ptr: *mut T, // base address for the register
+    backend: HardwareMmio, // the atomic accesses always go to the hardware
+}
+
+/// This struct allows write only access to a register and keeps a shadow copy of the value last written. As the
+/// register could not be read back, the functions updating single fields like ``modify`` work on the shadow copy and
+/// write the whole updated value to the register.
+///
+/// # Hint
+/// The shadow copy only reflects the register contents if all writes to the register go through the same instance
+/// of this struct. Therefore it is meant to be stored within the driver using the register and not to be used with
+/// [define_mmio_register], as the register defined there is created anew on each use.
+///
+/// # Example
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     PWM_CTL<WriteOnly<u32>@(0x3F20_C000)> {
+///         PWEN1 OFFSET(0),
+///         MODE1 OFFSET(1),
+///         PWEN2 OFFSET(8)
+///     }
+/// );
+///
+/// fn main() {
+///     let pwm_ctl = WriteOnlyShadow::<u32>::with_reset_value(PWM_CTL::Register.address(), 0);
+///     pwm_ctl.modify(PWM_CTL::PWEN1, 1);
+///     // channel 1 remains enabled as the shadow copy is updated with channel 2
+///     pwm_ctl.modify(PWM_CTL::PWEN2, 1);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct WriteOnlyShadow<T: RegisterType> {
+    ptr: *mut T, // base address for the register
+    shadow: Cell<T>, // the value last written to the register
+}
+
+/// The order ``Split64`` accesses the two halves of the register in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitOrder {
+    /// Access the lower half at the register address before the upper half
+    LowFirst,
+    /// Access the upper half 4 bytes above the register address before the lower half
+    HighFirst,
+}
+
+/// This struct allows read/write access to a 64 bit register on a peripheral bus that only supports 32 bit accesses,
+/// where a 64 bit access faults. Each access is split into two 32 bit accesses of the lower half at the register
+/// address and the upper half 4 bytes above it, performed in the [SplitOrder] given. The registers defined with
+/// [define_mmio_register] access the lower half first.
+///
+/// # Hint
+/// The two accesses are not atomic. A register the hardware updates in between, like a running counter, could be
+/// read with halves that do not belong together. [RegisterPair] reads such a register consistently.
+///
+/// # Example
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     TIMER_CMP<Split64<u64>@(0x3F00_3014)>
+/// );
+///
+/// fn main() {
+///     // written as 0x0 to 0x3F00_3014 followed by 0x1 to 0x3F00_3018
+///     TIMER_CMP::Register.set(0x1_0000_0000);
+///     // the peripheral latches the value on the access of the upper half
+///     let latched = Split64::<u64>::with_order(0x3F00_3014, SplitOrder::HighFirst);
+///     let value = latched.get();
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Split64<T: RegisterType, B = HardwareMmio> {
+    ptr: *mut T, // base address for the register, the address of the lower half
+    backend: B, // the memory access of the register
+    order: SplitOrder, // the order the halves are accessed in
+}
+
+/// This struct provides zero sized access to a register, where the address is part of the type. The register access
+/// type ``A`` with the register width is created on each access from the ``ADDR`` given. It dereferences to this
+/// register access type and provides all its functions.
+///
+/// # Example
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// const FOO: Reg<ReadWrite<u32>, 0x3F20_0000> = Reg::new();
+///
+/// fn main() {
+///     assert_eq!(core::mem::size_of_val(&FOO), 0);
+///     FOO.set(0x1F);
+/// }
+/// ```
+pub struct Reg<A, const ADDR: usize>(PhantomData<A>);
+
+impl<A, const ADDR: usize> Reg<A, ADDR> {
+    /// Create a new instance of the zero sized register.
+    #[allow(dead_code)]
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A, const ADDR: usize> Default for Reg<A, ADDR> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A, const ADDR: usize> Clone for Reg<A, ADDR> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<A: core::fmt::Debug, const ADDR: usize> core::fmt::Debug for Reg<A, ADDR>
+where
+    Self: Deref<Target = A>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// The base address of a peripheral that could be changed at runtime, e.g. once the MMU maps the peripherals to a
+/// different virtual address. Registers defined relative to this base address with ``@dyn(BASE + OFFSET)`` always
+/// access the register at the current base address.
+///
+/// # Example
+/// ```
+/// # use ruspiro_mmio_register::*;
+/// static PERIPHERAL_BASE: PeripheralBase = PeripheralBase::new(0x3F00_0000);
+///
+/// fn main() {
+///     assert_eq!(PERIPHERAL_BASE.init_once(0xFE00_0000), Ok(()));
+///     // a driver re-pointing the base address after the registers have been used is rejected
+///     assert_eq!(PERIPHERAL_BASE.init_once(0x3F00_0000), Err(BaseAlreadySet { base: 0xFE00_0000 }));
+///     assert_eq!(PERIPHERAL_BASE.base(), 0xFE00_0000);
+/// }
+/// ```
+pub struct PeripheralBase {
+    base: AtomicUsize, // the current base address
+    set: AtomicBool, // whether the base address has been set since its creation
+}
+
+impl PeripheralBase {
+    /// Create a new peripheral base address with the address used until a different one is set.
+    #[allow(dead_code)]
+    pub const fn new(addr: usize) -> Self {
+        Self {
+            base: AtomicUsize::new(addr),
+            set: AtomicBool::new(false),
+        }
+    }
+
+    /// Set the base address of the peripheral. This is intended to be called once by the startup code before the
+    /// registers of the peripheral are accessed. In contrast to ``init_once`` this always replaces the base address.
+    #[inline]
+    #[allow(dead_code)]
+    pub fn set_base(&self, addr: usize) {
+        self.set.store(true, Ordering::Release);
+        self.base.store(addr, Ordering::Release);
+    }
+
+    /// Set the base address of the peripheral only if it has not been set before, neither with ``init_once`` nor with
+    /// ``set_base``. This prevents the base address from being re-pointed after drivers have accessed the registers
+    /// at the base address set first.
+    ///
+    /// # Errors
+    /// Returns [BaseAlreadySet] with the current base address if the base address has already been set. The base
+    /// address is left unchanged.
+    #[inline]
+    #[allow(dead_code)]
+    pub fn init_once(&self, addr: usize) -> Result<(), BaseAlreadySet> {
+        if self.set.swap(true, Ordering::AcqRel) {
+            return Err(BaseAlreadySet { base: self.base() });
+        }
+        self.base.store(addr, Ordering::Release);
+        Ok(())
+    }
+
+    /// Provide the current base address of the peripheral.
+    #[inline]
+    #[allow(dead_code)]
+    pub fn base(&self) -> usize {
+        self.base.load(Ordering::Acquire)
+    }
+}
+
+/// The error of setting the base address of a peripheral with ``init_once`` that has already been set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BaseAlreadySet {
+    /// The base address the peripheral is already set to
+    pub base: usize,
+}
+
+impl core::fmt::Display for BaseAlreadySet {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "the peripheral base address is already set to {:#x}", self.base)
+    }
+}
+
+/// Confirmation of the caller that a register documented as read only is writeable on the silicon revision used. It
+/// is required to upgrade a [ReadOnly] register to a [ReadWrite] register with ``try_as_read_write``.
+///
+/// # Example
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// fn main() {
+///     let revision = ReadOnly::<u32>::new(0x3F20_0000);
+///     // the register is writeable since revision B0 of the chip used
+///     let vouched = unsafe { WriteOverride::vouch() };
+///     if let Some(revision) = revision.try_as_read_write(vouched) {
+///         revision.set(0x1F);
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct WriteOverride(());
+
+impl WriteOverride {
+    /// Vouch for the register to be upgraded being writeable.
+    ///
+    /// # Safety
+    /// Writing to a register that is read only on the silicon revision used may be ignored, fault or have side
+    /// effects that are not documented. The caller has to ensure the register is actually writeable.
+    #[allow(dead_code)]
+    pub const unsafe fn vouch() -> Self {
+        Self(())
+    }
+}
+
+/// A 64 bit value, like a counter or an address, that is provided by two 32 bit registers containing its lower and
+/// upper half.
+///
+/// # Example
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// // the free running counter of the system timer
+/// const SYS_TIMER: RegisterPair<ReadOnly<u32>> =
+///     RegisterPair::new(ReadOnly::<u32>::new(0x3F00_3004), ReadOnly::<u32>::new(0x3F00_3008));
+///
+/// fn main() {
+///     let now = SYS_TIMER.read_u64();
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RegisterPair<R> {
+    lo: R,
+    hi: R,
+}
+
+impl<R> RegisterPair<R> {
+    /// Create the pair from the register containing the lower half and the one containing the upper half
+    #[allow(dead_code)]
+    pub const fn new(lo: R, hi: R) -> Self {
+        Self { lo, hi }
+    }
+}
+
+impl<R: Readable<Width = u32>> RegisterPair<R> {
+    /// Read the 64 bit value. The upper half is read before and after the lower half. If the lower half rolled over
+    /// between those reads, the upper half has changed and the lower half is read again. This prevents combining the
+    /// lower half with an upper half that is off by one, as two plain reads of a running counter would do.
+    #[inline]
+    #[allow(dead_code)]
+    pub fn read_u64(&self) -> u64 {
+        let mut hi = self.hi.get();
+        loop {
+            let lo = self.lo.get();
+            let hi_again = self.hi.get();
+            if hi_again == hi {
+                return (u64::from(hi) << 32) | u64::from(lo);
+            }
+            hi = hi_again;
+        }
+    }
+}
+
+impl<R: Writeable<Width = u32>> RegisterPair<R> {
+    /// Write the 64 bit value. The lower half is written before the upper half.
+    #[inline]
+    #[allow(dead_code)]
+    pub fn write_u64(&self, value: u64) {
+        self.lo.set(value as u32);
+        self.hi.set((value >> 32) as u32);
+    }
+}
+
+/// Access the register at an address only known at runtime without defining a register for it, e.g. for ad-hoc
+/// accesses during the bring-up of a board. The closure is called with a temporary read/write accessor to the
+/// register that is dropped once the closure returns. The value returned by the closure is passed through.
+///
+/// # Hint
+/// The caller is responsible that ``addr`` is the address of a valid register of the width ``T``, as accessing any
+/// other address may fault or change the state of an unrelated peripheral.
+///
+/// # Panics
+/// In debug builds this panics if ``addr`` is not aligned to the register width.
+///
+/// # Example
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// fn main() {
+///     let gpio_base = 0x3F20_0000;
+///     let level = with_mmio::<u32, _>(gpio_base + 0x34, |lev| lev.get());
+///     with_mmio::<u32, _>(gpio_base + 0x1C, |set| set.set(1 << 17));
+/// }
+/// ```
+#[inline]
+#[allow(dead_code)]
+pub fn with_mmio<T: RegisterType, R>(addr: usize, f: impl FnOnce(&ReadWrite<T>) -> R) -> R {
+    debug_assert!(
+        addr & (core::mem::align_of::<T>() - 1) == 0,
+        "register address is not aligned"
+    );
+    let register = ReadWrite {
+        ptr: addr as *mut T,
+        backend: HardwareMmio,
+    };
+    f(&register)
+}
+
+/// Create a register field from the inclusive range of bits it covers, as given in most datasheets.
+///
+/// # Example
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// // the same as register_field!(u32, BAR, 4, 4)
+/// let bar = RegisterField::<u32>::from_range(4..=7);
+/// ```
+pub trait FromRange: Sized {
+    /// Create the register field covering the bits of the given range.
+    ///
+    /// # Panics
+    /// This panics if the range is descending or exceeds the register width.
+    fn from_range(range: RangeInclusive<u32>) -> Self;
+}
+
+/// The error of writing a value to a register field that does not fit into the width of the field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValueError<T: RegisterType> {
+    /// The value that does not fit into the field
+    pub value: T,
+    /// The width of the field in bits
+    pub bits: u32,
+}
+
+impl<T: RegisterType + core::fmt::LowerHex> core::fmt::Display for ValueError<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "value {:#x} does not fit into a register field of {} bits", self.value, self.bits)
+    }
+}
+
+/// The error of writing a value to a register field that is none of the specific values defined for the field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidEncoding<T: RegisterType> {
+    /// The value that is not defined for the field
+    pub value: T,
+}
+
+impl<T: RegisterType + core::fmt::LowerHex> core::fmt::Display for InvalidEncoding<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "value {:#x} is none of the values defined for the register field", self.value)
+    }
+}
+
+/// Create a register field value only if the value fits into the width of the field.
+///
+/// # Example
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         BAR OFFSET(4) BITS(2)
+///     }
+/// );
+///
+/// fn main() {
+///     assert!(RegisterFieldValue::<u32>::try_new(FOO::BAR, 0b100).is_err());
+/// }
+/// ```
+pub trait TryNew<T: RegisterType>: Sized {
+    /// Create the register field value or return the error if the value has bits set above the width of the field.
+    fn try_new(field: RegisterField<T>, value: T) -> Result<Self, ValueError<T>>;
+}
+
+/// The value of one or more register fields bound to the register the fields belong to. The specific field values and
+/// the ``with_value`` functions generated by [define_mmio_register] provide the field values bound to the ``Values``
+/// snapshot type of their register. The register only accepts the field values of its own fields, so passing the
+/// value of a field of a different register with the same width fails to compile.
+///
+/// # Example
+/// ```compile_fail
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         ENABLE OFFSET(0) [ON = 1]
+///     },
+///     OTHER<ReadWrite<u32>@(0x3F20_0004)> {
+///         FLAG OFFSET(0) [ON = 1]
+///     }
+/// );
+///
+/// fn main() {
+///     FOO::Register.write_value(OTHER::FLAG::ON);
+/// }
+/// ```
+pub struct TypedFieldValue<R, T: RegisterType> {
+    value: RegisterFieldValue<T>,
+    register: PhantomData<fn() -> R>, // the register the field value belongs to
+}
+
+macro_rules! typedfieldvalue_impl {
+    ($( $t:ty ),*) => { $(
+        impl<R> TypedFieldValue<R, $t> {
+            /// Bind the register field value to the register ``R``
+            #[allow(dead_code)]
+            pub const fn new(value: RegisterFieldValue<$t>) -> Self {
+                Self {
+                    value,
+                    register: PhantomData,
+                }
+            }
+
+            /// Provide the register field value not bound to any register, e.g. to pass it to a register access
+            /// struct directly.
+            #[inline]
+            #[allow(dead_code)]
+            pub const fn value(self) -> RegisterFieldValue<$t> {
+                self.value
+            }
+
+            /// Provide the raw value of the field value shifted to the field position
+            #[inline]
+            #[allow(dead_code)]
+            pub const fn raw_value(&self) -> $t {
+                self.value.raw_value()
+            }
+
+            /// Provide the mask of the fields covered by the field value
+            #[inline]
+            #[allow(dead_code)]
+            pub const fn mask(&self) -> $t {
+                self.value.mask()
+            }
+
+            /// Combine two field values of the same register into one covering the fields of both. This is the
+            /// same as ``a | b`` but usable in ``const`` context, e.g. to define the configuration of a register
+            /// as a ``const`` or ``static``. If both field values cover the same field the values are OR'ed.
+            #[inline]
+            #[allow(dead_code)]
+            pub const fn combine(self, other: Self) -> Self {
+                Self::new(RegisterFieldValue::<$t>::new(
+                    RegisterField::<$t>::new(self.mask() | other.mask(), 0),
+                    (self.raw_value() & self.mask()) | (other.raw_value() & other.mask()),
+                ))
+            }
+        }
+
+        impl<R> Clone for TypedFieldValue<R, $t> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<R> Copy for TypedFieldValue<R, $t> {}
+
+        impl<R> core::fmt::Debug for TypedFieldValue<R, $t> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Debug::fmt(&self.value, f)
+            }
+        }
+
+        impl<R> core::ops::BitOr for TypedFieldValue<R, $t> {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self {
+                // the masks are combined explicitly, so a field with the value 0 is still covered and cleared
+                self.combine(rhs)
+            }
+        }
+
+        impl<R> From<TypedFieldValue<R, $t>> for RegisterFieldValue<$t> {
+            fn from(value: TypedFieldValue<R, $t>) -> RegisterFieldValue<$t> {
+                value.value
+            }
+        }
+    )* };
+}
+typedfieldvalue_impl![u8, u16, u32, u64];
+
+/// A single field of a register accessed like a register of its own. The view provides ``get`` and ``set`` working
+/// on the value of the field only, so a part of a register can be handed to code that shall not access the other
+/// fields. Writing the field value is a read-modify-write of the register keeping the other bits untouched. Bits of
+/// the value beyond the width of the field are ignored.
+///
+/// # Example
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         ENABLE OFFSET(0),
+///         DIVIDER OFFSET(4) BITS(8)
+///     }
+/// );
+///
+/// fn set_speed(divider: RegisterView<ReadWrite<u32>, u32>) {
+///     divider.set(0x1F);
+/// }
+///
+/// fn main() {
+///     set_speed(FOO::Register.view(FOO::DIVIDER));
+/// }
+/// ```
+///
+/// The other fields of the register keep their value when writing through the view:
+/// ```
+/// # use ruspiro_mmio_register::*;
+/// # use ruspiro_mmio_register::backend::Memory;
+/// fn main() {
+///     let memory = Memory::<1>::new(0x1000);
+///     let register = ReadWrite::<u32, _>::with_backend(0x1000, &memory);
+///     register.set(0xFFFF);
+///
+///     let divider = register.view(RegisterField::<u32>::new(0xFF, 4));
+///     divider.set(0x1A5);
+///     assert_eq!(divider.get(), 0xA5);
+///     assert_eq!(register.get(), 0xFA5F);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RegisterView<A, T: RegisterType> {
+    register: A,
+    field: RegisterField<T>,
+}
+
+macro_rules! registerview_impl {
+    ($( $t:ty ),*) => { $(
+        impl<A> RegisterView<A, $t> {
+            /// Provide the field of the register this view accesses
+            #[inline]
+            #[allow(dead_code)]
+            pub fn field(&self) -> RegisterField<$t> {
+                self.field
+            }
+        }
+
+        impl<A: Readable<Width = $t>> RegisterView<A, $t> {
+            /// Read the value of the field
+            #[inline]
+            #[allow(dead_code)]
+            pub fn get(&self) -> $t {
+                (self.register.get() & self.field.mask()) >> self.field.shift()
+            }
+        }
+
+        impl<A: Modifiable<Width = $t>> RegisterView<A, $t> {
+            /// Write the value of the field while keeping all other bits of the register. Bits of the value beyond
+            /// the width of the field are ignored.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn set(&self, value: $t) {
+                self.register.modify(self.field, value);
+            }
+        }
+    )* };
+}
+registerview_impl![u8, u16, u32, u64];
+
+/// A register accessed with a smaller width than the register slot it sits in, e.g. an 8 bit register of a device
+/// attached through a bus that is mapped into a 32 bit MMIO window where only the low byte is meaningful. The register
+/// is still accessed with the width of the slot. ``get`` truncates the value read to the lower bits fitting into
+/// ``U``. ``set`` is a read-modify-write of the register keeping the upper bits of the slot, while
+/// ``set_zero_extended`` writes the value with the upper bits of the slot cleared, e.g. to a write only register.
+///
+/// # Example
+/// ```
+/// # use ruspiro_mmio_register::*;
+/// # use ruspiro_mmio_register::backend::Memory;
+/// fn main() {
+///     let memory = Memory::<1>::new(0x1000);
+///     let slot = ReadWrite::<u32, _>::with_backend(0x1000, &memory);
+///     slot.set(0x1234_5678);
+///
+///     let device = slot.narrow::<u8>();
+///     assert_eq!(device.get(), 0x78);
+///     device.set(0xA5);
+///     assert_eq!(slot.get(), 0x1234_56A5);
+///     device.set_zero_extended(0x5A);
+///     assert_eq!(slot.get(), 0x5A);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct NarrowView<A, T: RegisterType, U: RegisterType> {
+    register: A,
+    width: PhantomData<(T, U)>, // the width of the register slot and the narrow width it is accessed with
+}
+
+macro_rules! narrowview_impl {
+    ($t:ty => $( $u:ty ),*) => { $(
+        impl<A: Readable<Width = $t>> NarrowView<A, $t, $u> {
+            /// Read the register and truncate the value to the narrow width
+            #[inline]
+            #[allow(dead_code)]
+            pub fn get(&self) -> $u {
+                self.register.get() as $u
+            }
+        }
+
+        impl<A: Modifiable<Width = $t>> NarrowView<A, $t, $u> {
+            /// Write the value to the lower bits of the register while keeping the upper bits of the slot
+            #[inline]
+            #[allow(dead_code)]
+            pub fn set(&self, value: $u) {
+                self.register.modify(RegisterField::<$t>::new(<$u>::MAX as $t, 0), value as $t);
+            }
+        }
+
+        impl<A: Writeable<Width = $t>> NarrowView<A, $t, $u> {
+            /// Write the value zero-extended to the width of the register, so the upper bits of the slot are cleared
+            #[inline]
+            #[allow(dead_code)]
+            pub fn set_zero_extended(&self, value: $u) {
+                self.register.set(value as $t);
+            }
+        }
+    )* };
+}
+narrowview_impl![u16 => u8];
+narrowview_impl![u32 => u8, u16];
+narrowview_impl![u64 => u8, u16, u32];
+
+/// The value of a register field as a type of its own. [define_mmio_register] generates this type for the fields
+/// marked with ``TYPED``, named after the field like ``BarValue`` for the field ``BAR``. The registers read and
+/// update those fields with ``read_typed`` and ``modify_typed``, so values of different fields of the same width
+/// could not be mixed up. Only the values of the fields of the register accessed are accepted.
+pub trait TypedField: Copy {
+    /// The type representing the register width
+    type Width: RegisterType;
+
+    /// The ``Values`` snapshot type of the register the field belongs to
+    type Register;
+
+    /// The register field the value belongs to
+    const FIELD: RegisterField<Self::Width>;
+
+    /// Create the field value from the raw value of the field.
+    fn from_raw(raw: Self::Width) -> Self;
+
+    /// Provide the raw value of the field.
+    fn raw(self) -> Self::Width;
+}
+
+mod sealed {
+    /// Supertrait of the register access traits. As it could not be named outside of this crate, the access traits
+    /// are only implemented for the register access types provided here. This prevents implementing ``Readable``
+    /// for a type that is not meant to be read, like a custom wrapper of a write only register.
+    pub trait Sealed {}
+}
+
+/// Registers that could be read from. This allows generic code to work with any readable register regardless of its
+/// access type. This trait is sealed and implemented for the register access types of this crate only.
+///
+/// ```compile_fail
+/// # use ruspiro_mmio_register::*;
+/// struct Status(WriteOnly<u32>);
+///
+/// impl Readable for Status {
+///     type Width = u32;
+///
+///     fn get(&self) -> u32 {
+///         0
+///     }
+/// }
+/// ```
+pub trait Readable: sealed::Sealed {
+    /// The type representing the register width
+    type Width: RegisterType;
+
+    /// Read raw content of the register.
+    fn get(&self) -> Self::Width;
+}
+
+/// Registers that could be written to. This allows generic code to work with any writeable register regardless of
+/// its access type. This trait is sealed and implemented for the register access types of this crate only.
+pub trait Writeable: sealed::Sealed {
+    /// The type representing the register width
+    type Width: RegisterType;
+
+    /// Write raw content value to the register.
+    fn set(&self, value: Self::Width);
+}
+
+/// Registers that could be updated with a read-modify-write. This allows generic code to work with any of those
+/// registers regardless of its access type.
+///
+/// # Example
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         ENABLE OFFSET(0)
+///     },
+///     BAR<AtomicReadWrite<u32>@(0x3F20_0004)> {
+///         ENABLE OFFSET(3)
+///     }
+/// );
+///
+/// fn enable(register: &impl Modifiable<Width = u32>, field: RegisterField<u32>) {
+///     register.modify(field, 1);
+/// }
+///
+/// fn main() {
+///     enable(&*FOO::Register, FOO::ENABLE);
+///     enable(&*BAR::Register, BAR::ENABLE);
+/// }
+/// ```
+pub trait Modifiable: Readable {
+    /// Udate a register field with a given value. The bits outside of this field remains untouched.
+    /// The function returns the register raw value set has been set with this update
+    fn modify(&self, field: RegisterField<Self::Width>, value: Self::Width) -> Self::Width;
+
+    /// Udate one or more register fields with a given register field value. The bits outside of those fields remains
+    /// untouched. The function returns the register field value the fields contained before this update
+    fn replace_value(&self, fieldvalue: RegisterFieldValue<Self::Width>) -> RegisterFieldValue<Self::Width>;
+
+    /// Update the register with the raw value calculated by the closure from its current raw value. This is the
+    /// read-modify-write primitive of the access type, so it is atomic or performed in a critical section where the
+    /// access type provides this. The closure might be called more than once and should not have side effects.
+    /// The function returns the register raw value before and after this update as ``(old, new)``.
+    fn rmw<F: Fn(Self::Width) -> Self::Width>(&self, f: F) -> (Self::Width, Self::Width);
+}
+
+/// Read the raw value from the memory mapped register. With the ``mock`` feature enabled the value is read from
+/// the mocked address space instead.
+#[inline(always)]
+fn mmio_read<T: RegisterType + Into<u64>>(ptr: *const T) -> T {
+    #[cfg(not(feature = "mock"))]
+    let value = unsafe { read_volatile(ptr) };
+    #[cfg(feature = "mock")]
+    let value = mock::read(ptr as usize);
+
+    trace_read(ptr as usize, value);
+    value
+}
+
+/// Write the raw value to the memory mapped register. With the ``mock`` feature enabled the value is written to
+/// the mocked address space instead.
+#[inline(always)]
+fn mmio_write<T: RegisterType + Into<u64>>(ptr: *mut T, value: T) {
+    trace_write(ptr as usize, value);
+
+    #[cfg(not(feature = "mock"))]
+    unsafe {
+        write_volatile(ptr, value)
+    }
+    #[cfg(feature = "mock")]
+    mock::write(ptr as usize, value)
+}
+
+/// Pass a register read to the hook installed with the ``trace`` feature enabled. Without the feature this is a
+/// no-op.
+#[inline(always)]
+fn trace_read<T: Into<u64>>(_addr: usize, _value: T) {
+    #[cfg(feature = "trace")]
+    trace::call(trace::AccessKind::Read, _addr, _value.into());
+}
+
+/// Pass a register write to the hook installed with the ``trace`` feature enabled. Without the feature this is a
+/// no-op.
+#[inline(always)]
+fn trace_write<T: Into<u64>>(_addr: usize, _value: T) {
+    #[cfg(feature = "trace")]
+    trace::call(trace::AccessKind::Write, _addr, _value.into());
+}
+
+/// The layout of a register field as provided by the ``FIELDS`` table of each register defined with
+/// [define_mmio_register]. This allows tooling like a memory map documentation generator to enumerate the fields of a
+/// register without parsing the register definition.
+///
+/// # Example
+/// ```
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         ENABLE OFFSET(0),
+///         DIV OFFSET(4) BITS(8)
+///     }
+/// );
+///
+/// fn main() {
+///     for field in FOO::FIELDS {
+///         // e.g. write the field to the generated documentation
+///         let _bits = field.offset..field.offset + field.width;
+///     }
+///     assert_eq!(FOO::FIELDS[1], FieldInfo::new("DIV", 4, 8));
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldInfo {
+    /// The name of the field as given in the register definition
+    pub name: &'static str,
+    /// The position of the lowest bit of the field within the register
+    pub offset: u32,
+    /// The number of bits the field covers
+    pub width: u32,
+}
+
+impl FieldInfo {
+    /// Create the layout of a field
+    #[allow(dead_code)]
+    pub const fn new(name: &'static str, offset: u32, width: u32) -> Self {
+        Self { name, offset, width }
+    }
+}
+
+/// Collect the ``N`` layouts of the fields that are compiled in into the ``FIELDS`` table of a register.
+#[doc(hidden)]
+pub const fn compact_fields<const N: usize>(fields: &[Option<FieldInfo>]) -> [FieldInfo; N] {
+    let mut compacted = [FieldInfo::new("", 0, 0); N];
+    let mut count = 0;
+    let mut index = 0;
+    while index < fields.len() {
+        if let Some(field) = fields[index] {
+            compacted[count] = field;
+            count += 1;
+        }
+        index += 1;
+    }
+    compacted
+}
+
+/// Check that each encoding of a field of ``bits`` bits is contained in the values exactly once. This is evaluated at
+/// compile time for the fields marked ``EXHAUSTIVE``.
+#[doc(hidden)]
+pub const fn names_all_encodings(values: &[u64], bits: u32) -> bool {
+    if bits >= 64 || values.len() as u64 != 1 << bits {
+        return false;
+    }
+    let mut index = 0;
+    while index < values.len() {
+        if values[index] >> bits != 0 {
+            return false;
+        }
+        let mut other = index + 1;
+        while other < values.len() {
+            if values[other] == values[index] {
+                return false;
+            }
+            other += 1;
+        }
+        index += 1;
+    }
+    true
+}
+
+/// Count how often ``name`` is contained in ``names``. This is evaluated at compile time to reject a register
+/// defining the same field more than once.
+#[doc(hidden)]
+pub const fn count_name(name: &str, names: &[&str]) -> usize {
+    let name = name.as_bytes();
+    let mut count = 0;
+    let mut index = 0;
+    while index < names.len() {
+        let other = names[index].as_bytes();
+        if other.len() == name.len() {
+            let mut pos = 0;
+            while pos < name.len() && name[pos] == other[pos] {
+                pos += 1;
+            }
+            if pos == name.len() {
+                count += 1;
+            }
+        }
+        index += 1;
+    }
+    count
+}
+
+/*************** internal used macros to ease implementation ******************/
+macro_rules! registernew_impl {
+    ($t:ty) => {
+        /// Create a new instance of the register access struct.
+        ///
+        /// # Panics
+        /// In debug builds this panics if ``addr`` is not aligned to the register width.
+        #[allow(dead_code)]
+        pub const fn new(addr: usize) -> Self {
+            debug_assert!(addr % core::mem::align_of::<$t>() == 0, "register address is not aligned");
+            Self {
+                // the pointer is mutable or const depending on the access type
+                ptr: addr as _,
+                backend: HardwareMmio,
+            }
+        }
+
+        registertrynew_impl!($t);
+    };
+}
+
+macro_rules! registertrynew_impl {
+    ($t:ty) => {
+        /// Create a new instance of the register access struct from a pointer to the register.
+        ///
+        /// # Panics
+        /// In debug builds this panics if ``ptr`` is not aligned to the register width.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn new_nonnull(ptr: NonNull<$t>) -> Self {
+            Self::new(ptr.as_ptr() as usize)
+        }
+
+        /// Create a new instance of the register access struct if ``addr`` is a valid register address. Returns
+        /// ``None`` if ``addr`` is 0 or not aligned to the register width, as accessing a misaligned register faults.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn try_new(addr: usize) -> Option<Self> {
+            if addr == 0 || addr % core::mem::align_of::<$t>() != 0 {
+                None
+            } else {
+                Some(Self::new(addr))
+            }
+        }
+    };
+}
+
+macro_rules! registerptr_impl {
+    ($t:ty) => {
+        registerptr_impl!($t, mut);
+    };
+    ($t:ty, $kind:tt) => {
+        /// Provide the address of the register. This does not access the register.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn address(&self) -> usize {
+            self.ptr as usize
+        }
+
+        /// Provide the raw pointer to the register. This does not access the register.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn as_ptr(&self) -> *$kind $t {
+            self.ptr
+        }
+    };
+}
+
+macro_rules! registeroffset_impl {
+    ($t:ty) => {
+        /// Provide an accessor of the same type to the register ``count`` registers away from this one, so the
+        /// address is ``count * size_of::<T>()`` bytes away. This does not access the register.
+        ///
+        /// # Hint
+        /// The caller is responsible that the resulting address is a valid register of the same type.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn offset(&self, count: isize) -> Self {
+            Self {
+                ptr: self.ptr.wrapping_offset(count),
+                backend: self.backend.clone(),
+            }
+        }
+    };
+}
+
+macro_rules! registerbackend_impl {
+    ($t:ty) => {
+        /// Create a new instance of the register access struct accessing the register through the ``backend``
+        /// given instead of the memory mapped I/O address, e.g. an in-memory backend for testing.
+        #[allow(dead_code)]
+        pub const fn with_backend(addr: usize, backend: B) -> Self {
+            Self {
+                ptr: addr as _,
+                backend,
+            }
+        }
+    };
+}
+
+macro_rules! registerviews_impl {
+    ($t:ty, $b:ty, $readonly:ident, $writeonly:ident) => {
+        /// Provide a read only view of the register at the same address. This allows to hand out the register to
+        /// code that shall not write to it.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn as_read_only(&self) -> $readonly<$t, $b> {
+            $readonly::<$t, $b>::with_backend(self.address(), self.backend.clone())
+        }
+
+        /// Provide a write only view of the register at the same address. This allows to hand out the register to
+        /// code that shall not read from it.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn as_write_only(&self) -> $writeonly<$t, $b> {
+            $writeonly::<$t, $b>::with_backend(self.address(), self.backend.clone())
+        }
+    };
+}
+
+macro_rules! registernarrow_impl {
+    ($t:ty) => {
+        /// Provide a view of the register accessing it with the smaller width ``U``, e.g. an 8 bit device register
+        /// in a 32 bit MMIO slot. See [NarrowView] for how the upper bits of the slot are handled.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn narrow<U: RegisterType>(&self) -> NarrowView<Self, $t, U> {
+            NarrowView {
+                register: self.clone(),
+                width: PhantomData,
+            }
+        }
+    };
+}
+
+macro_rules! registerget_impl {
+    ($t:ty) => {
+        /// Read raw content of a register.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn get(&self) -> $t {
+            self.backend.read(self.address())
+        }
+
+        registerread_impl!($t);
+    };
+}
+
+macro_rules! registergetbe_impl {
+    ($t:ty) => {
+        /// Read raw content of a big-endian register converted into the host endianness.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn get(&self) -> $t {
+            <$t>::from_be(self.backend.read(self.address()))
+        }
+
+        registerread_impl!($t);
+    };
+}
+
+macro_rules! sealed_impl {
+    ($access:ident, $t:ty $(, $b:ident)?) => {
+        impl$(<$b>)? sealed::Sealed for $access<$t $(, $b)?> {}
+    };
+}
+
+macro_rules! readable_impl {
+    ($access:ident, $t:ty $(, $b:ident)?) => {
+        impl$(<$b: MmioAccess>)? Readable for $access<$t $(, $b)?> {
+            type Width = $t;
+
+            #[inline]
+            fn get(&self) -> $t {
+                $access::<$t $(, $b)?>::get(self)
+            }
+        }
+    };
+}
+
+macro_rules! writeable_impl {
+    ($access:ident, $t:ty $(, $b:ident)?) => {
+        impl$(<$b: MmioAccess>)? Writeable for $access<$t $(, $b)?> {
+            type Width = $t;
+
+            #[inline]
+            fn set(&self, value: $t) {
+                $access::<$t $(, $b)?>::set(self, value)
+            }
+        }
+    };
+}
+
+macro_rules! modifiable_impl {
+    ($access:ident, $t:ty $(, $b:ident)?) => {
+        impl$(<$b: MmioAccess>)? Modifiable for $access<$t $(, $b)?> {
+            #[inline]
+            fn modify(&self, field: RegisterField<$t>, value: $t) -> $t {
+                $access::<$t $(, $b)?>::modify(self, field, value)
+            }
+
+            #[inline]
+            fn replace_value(&self, fieldvalue: RegisterFieldValue<$t>) -> RegisterFieldValue<$t> {
+                $access::<$t $(, $b)?>::replace_value(self, fieldvalue)
+            }
+
+            #[inline]
+            fn rmw<F: Fn($t) -> $t>(&self, f: F) -> ($t, $t) {
+                $access::<$t $(, $b)?>::rmw(self, f)
+            }
+        }
+    };
+}
+
+macro_rules! reg_impl {
+    ($access:ident, $( $t:ty ),*) => { $(
+        impl<const ADDR: usize> Reg<$access<$t>, ADDR> {
+            // the register access type is promoted to a constant of static lifetime to be dereferenced to
+            const ACCESS: &'static $access<$t> = &$access::<$t>::new(ADDR);
+        }
+
+        impl<const ADDR: usize> Deref for Reg<$access<$t>, ADDR> {
+            type Target = $access<$t>;
+
+            #[inline]
+            fn deref(&self) -> &$access<$t> {
+                Self::ACCESS
+            }
+        }
+    )* };
+}
+
+macro_rules! fromrange_impl {
+    ($( $t:ty ),*) => { $(
+        impl FromRange for RegisterField<$t> {
+            fn from_range(range: RangeInclusive<u32>) -> Self {
+                let (low, high) = (*range.start(), *range.end());
+                assert!(low <= high, "the bit range of the register field is descending");
+                assert!(high < <$t>::BITS, "the bit range of the register field exceeds the register width");
+                RegisterField::<$t>::new(!0 >> (<$t>::BITS - 1 - (high - low)), low as $t)
+            }
+        }
+    )* };
+}
+fromrange_impl![u8, u16, u32, u64];
+
+macro_rules! trynew_impl {
+    ($( $t:ty ),*) => { $(
+        impl TryNew<$t> for RegisterFieldValue<$t> {
+            fn try_new(field: RegisterField<$t>, value: $t) -> Result<Self, ValueError<$t>> {
+                let field_mask = field.mask() >> field.shift();
+                if value & !field_mask != 0 {
+                    Err(ValueError {
+                        value,
+                        bits: field_mask.count_ones(),
+                    })
+                } else {
+                    Ok(RegisterFieldValue::<$t>::new(field, value))
+                }
+            }
+        }
+    )* };
+}
+trynew_impl![u8, u16, u32, u64];
+
+macro_rules! registerread_impl {
+    ($t:ty) => {
+        /// Read raw content of a register with a compiler fence of the given ``Ordering`` before and after the read.
+        /// This prevents the compiler from moving memory accesses of the surrounding code across the register read.
+        /// ``Acquire`` is typically used when reading a status register that signals the device has written data
+        /// to memory, so the data is not read before the status. ``SeqCst`` prevents any reordering.
+        ///
+        /// # Panics
+        /// This panics if ``order`` is ``Relaxed``.
+        ///
+        /// # Hint
+        /// The fence does only restrict the compiler. Reordering by the CPU requires memory barriers.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn get_ordered(&self, order: Ordering) -> $t {
+            compiler_fence(order);
+            let value = self.get();
+            compiler_fence(order);
+            value
+        }
+
+        /// Read raw content of a register with a data memory barrier before and after the read. This orders the read
+        /// with the accesses to other peripherals before and after it.
+        #[cfg(feature = "arm-barriers")]
+        #[inline]
+        #[allow(dead_code)]
+        pub fn get_barrier(&self) -> $t {
+            barrier::data_memory_barrier();
+            let value = self.get();
+            barrier::data_memory_barrier();
+            value
+        }
+
+        /// Read the value of a specific register field
+        #[inline]
+        #[allow(dead_code)]
+        pub fn read(&self, field: RegisterField<$t>) -> $t {
+            let val = self.get();
+            (val & field.mask() ) >> field.shift() 
+        }
+
+        /// Read the value of the register into a RegisterFieldValue structure
+        #[inline]
+        #[allow(dead_code)]
+        pub fn read_value(&self, field: RegisterField<$t>) -> RegisterFieldValue<$t> {
+            RegisterFieldValue::<$t>::new(field, self.read(field))
+        }
+
+        /// Read the current contents of several register fields with a single read of the register into one
+        /// RegisterFieldValue covering all of them. This allows to save some fields of a register and restore them
+        /// later with ``modify_value`` without touching the other bits.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn read_values(&self, fields: &[RegisterField<$t>]) -> RegisterFieldValue<$t> {
+            let mask = fields.iter().fold(0, |mask, field| mask | field.mask());
+            // the mask covers all fields to read, so it is used as field without a shift
+            RegisterFieldValue::<$t>::new(RegisterField::<$t>::new(mask, 0), self.get())
+        }
+
+        /// Provide a view of a specific register field accessing it like a register of its own
+        #[inline]
+        #[allow(dead_code)]
+        pub fn view(&self, field: RegisterField<$t>) -> RegisterView<Self, $t> {
+            RegisterView {
+                register: self.clone(),
+                field,
+            }
+        }
+
+        /// Read raw content of a register converted into the type ``U``, e.g. a type of the driver representing the
+        /// register contents
+        #[inline]
+        #[allow(dead_code)]
+        pub fn read_into<U: From<$t>>(&self) -> U {
+            U::from(self.get())
+        }
+
+        /// Read the value of a specific register field converted into the type ``U``, e.g. a status ``enum`` of the
+        /// driver
+        #[inline]
+        #[allow(dead_code)]
+        pub fn read_field_into<U: From<$t>>(&self, field: RegisterField<$t>) -> U {
+            U::from(self.read(field))
+        }
+
+        /// Read raw content of a register into a type defined with the ``bitflags`` crate. Bits without a named
+        /// flag are retained. The flags need to have the width of the register, so their ``Bits`` type has to be
+        /// the type of the register.
+        #[cfg(feature = "bitflags")]
+        #[inline]
+        #[allow(dead_code)]
+        pub fn read_flags<F: bitflags::Flags<Bits = $t>>(&self) -> F {
+            F::from_bits_retain(self.get())
+        }
+
+        /// Check whether a register field is set. For fields covering more than one bit this is the case if any of
+        /// the bits is set to 1.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn is_set(&self, field: RegisterField<$t>) -> bool {
+            self.get() & field.mask() != 0
+        }
+
+        /// Check whether a register field is clear. For fields covering more than one bit this is the case if all of
+        /// the bits are set to 0.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn is_clear(&self, field: RegisterField<$t>) -> bool {
+            !self.is_set(field)
+        }
+
+        /// Fill the slice with raw values read from the register. All reads are from the same register, so this is
+        /// meant to drain a hardware FIFO.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn read_slice(&self, out: &mut [$t]) {
+            for value in out.iter_mut() {
+                *value = self.get();
+            }
+        }
+
+        /// Fill the slice with raw values read from consecutive registers starting at this one. In contrast to
+        /// ``read_slice`` the address advances by the register width for each value, so this is meant to read a
+        /// memory mapped table, e.g. a mailbox buffer.
+        ///
+        /// # Panics
+        /// In debug builds this panics if the slice is empty.
+        ///
+        /// # Hint
+        /// The caller is responsible that all addresses read are valid registers of the same type.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn read_block(&self, out: &mut [$t]) {
+            debug_assert!(!out.is_empty(), "register block is empty");
+            for (index, value) in out.iter_mut().enumerate() {
+                *value = self.offset(index as isize).get();
+            }
+        }
+
+        /// Check whether the single bit ``n`` of the register is set.
+        ///
+        /// # Panics
+        /// In debug builds this panics if ``n`` exceeds the register width.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn get_bit(&self, n: u32) -> bool {
+            debug_assert!(n < <$t>::BITS, "bit exceeds the register width");
+            self.get() & (1 << n) != 0
+        }
+
+        /// Read the ``len`` bits of the register starting at bit ``offset``. This allows to access bits of the register
+        /// that are not defined as a field, e.g. if their layout is only known at runtime.
+        ///
+        /// # Panics
+        /// In debug builds this panics if the bits exceed the register width.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn get_bits(&self, offset: u32, len: u32) -> $t {
+            debug_assert!(offset + len <= <$t>::BITS, "bits exceed the register width");
+            let mask = (!(0 as $t)).checked_shr(<$t>::BITS - len).unwrap_or(0);
+            self.get().checked_shr(offset).unwrap_or(0) & mask
+        }
+
+        /// Read the value of a specific register field storing a two's-complement value. The value is sign extended
+        /// from the top bit of the field. The field of ``n`` bits provides values in the range
+        /// ``-2^(n-1)..=2^(n-1)-1``, e.g. ``-8..=7`` for a 4 bit field. A field without any bit always provides 0.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn read_signed(&self, field: RegisterField<$t>) -> i64 {
+            let unused = 64 - field.mask().count_ones();
+            if unused == 64 {
+                return 0;
+            }
+            ((self.read(field) as u64) << unused) as i64 >> unused
+        }
+
+        /// Read a register field storing an unsigned fixed-point value with ``frac_bits`` fractional bits, e.g. a
+        /// clock divider with 12 integer and 12 fractional bits. The raw field value is scaled by ``2^-frac_bits``.
+        ///
+        /// # Panics
+        /// This panics if ``frac_bits`` is 64 or more.
+        #[cfg(feature = "float")]
+        #[inline]
+        #[allow(dead_code)]
+        pub fn read_fixed(&self, field: RegisterField<$t>, frac_bits: u32) -> f32 {
+            assert!(frac_bits < 64, "fractional bits exceed 63");
+            self.read(field) as f32 / (1u64 << frac_bits) as f32
+        }
+
+        /// Count the bits of a specific register field that are set, e.g. the number of active channels in a bitmap
+        /// status register.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn field_count_ones(&self, field: RegisterField<$t>) -> u32 {
+            (self.get() & field.mask()).count_ones()
+        }
+
+        /// Count the bits of a specific register field that are cleared. Only the bits of the field are counted.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn field_count_zeros(&self, field: RegisterField<$t>) -> u32 {
+            (!self.get() & field.mask()).count_ones()
+        }
+
+        /// Check whether a register field currently contains the given value
+        #[inline]
+        #[allow(dead_code)]
+        pub fn matches(&self, field: RegisterField<$t>, value: $t) -> bool {
+            self.read(field) == value
+        }
+
+        /// Check whether the register currently contains the given RegisterFieldValue. The bits not covered by the
+        /// field value are ignored. For RegisterFieldValues combined with logical OR all of the fields need to match.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn matches_value(&self, fieldvalue: RegisterFieldValue<$t>) -> bool {
+            self.get() & fieldvalue.mask() == fieldvalue.raw_value() & fieldvalue.mask()
+        }
+
+        /// Wait until the register field contains the given value. This busy-waits for ever if the field never
+        /// reaches the value, so ``wait_until_timeout`` should be preferred if the peripheral might not respond.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn wait_until(&self, field: RegisterField<$t>, value: $t) {
+            while self.read(field) != value {
+                core::hint::spin_loop();
+            }
+        }
+
+        /// Provide a future that resolves once the register field contains the given value. Each poll reads the
+        /// register once. While the field does not match the task is woken right away, so this yields to the other
+        /// tasks between the reads. Use ``wait_field_with`` to schedule the next read with a waker source instead.
+        #[cfg(feature = "async")]
+        #[inline]
+        #[allow(dead_code)]
+        pub fn wait_field(&self, field: RegisterField<$t>, value: $t) -> impl core::future::Future<Output = ()> + '_ {
+            self.wait_field_with(field, value, future::Yield)
+        }
+
+        /// Provide a future that resolves once the register field contains the given value. Each poll reads the
+        /// register once. While the field does not match the next poll is scheduled with ``repoll``.
+        #[cfg(feature = "async")]
+        #[inline]
+        #[allow(dead_code)]
+        pub fn wait_field_with<'a, P: future::Repoll + 'a>(
+            &'a self,
+            field: RegisterField<$t>,
+            value: $t,
+            repoll: P,
+        ) -> impl core::future::Future<Output = ()> + 'a {
+            future::WaitUntil::new(move || self.read(field) == value, repoll)
+        }
+
+        /// Wait until the register field contains the given value, but check the field at most ``max_iters`` times.
+        /// Returns ``Err`` if the field has not reached the value within the given number of checks.
+        #[inline]
+        #[allow(dead_code, clippy::result_unit_err)]
+        pub fn wait_until_timeout(&self, field: RegisterField<$t>, value: $t, max_iters: usize) -> Result<(), ()> {
+            for _ in 0..max_iters {
+                if self.read(field) == value {
+                    return Ok(());
+                }
+                core::hint::spin_loop();
+            }
+            Err(())
+        }
+
+        /// Wait until the register field contains a value greater than or equal to the given threshold, e.g. a
+        /// counter or a fill level rising over time. This busy-waits for ever if the field never reaches the
+        /// threshold, so ``wait_until_ge_timeout`` should be preferred if the peripheral might not respond.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn wait_until_ge(&self, field: RegisterField<$t>, value: $t) {
+            while self.read(field) < value {
+                core::hint::spin_loop();
+            }
+        }
+
+        /// Wait until the register field contains a value less than or equal to the given threshold, e.g. a fill
+        /// level falling over time. This busy-waits for ever if the field never reaches the threshold, so
+        /// ``wait_until_le_timeout`` should be preferred if the peripheral might not respond.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn wait_until_le(&self, field: RegisterField<$t>, value: $t) {
+            while self.read(field) > value {
+                core::hint::spin_loop();
+            }
+        }
+
+        /// Wait until the register field contains a value greater than or equal to the given threshold, but check
+        /// the field at most ``max_iters`` times. Returns ``Err`` if the field has not reached the threshold within
+        /// the given number of checks.
+        #[inline]
+        #[allow(dead_code, clippy::result_unit_err)]
+        pub fn wait_until_ge_timeout(&self, field: RegisterField<$t>, value: $t, max_iters: usize) -> Result<(), ()> {
+            for _ in 0..max_iters {
+                if self.read(field) >= value {
+                    return Ok(());
+                }
+                core::hint::spin_loop();
+            }
+            Err(())
+        }
+
+        /// Wait until the register field contains a value less than or equal to the given threshold, but check the
+        /// field at most ``max_iters`` times. Returns ``Err`` if the field has not reached the threshold within the
+        /// given number of checks.
+        #[inline]
+        #[allow(dead_code, clippy::result_unit_err)]
+        pub fn wait_until_le_timeout(&self, field: RegisterField<$t>, value: $t, max_iters: usize) -> Result<(), ()> {
+            for _ in 0..max_iters {
+                if self.read(field) <= value {
+                    return Ok(());
+                }
+                core::hint::spin_loop();
+            }
+            Err(())
+        }
+
+        /// Poll the register field until its value satisfies the condition given, but read the field at most
+        /// ``max_iters`` times. The field is read at least once. Returns the value satisfying the condition or
+        /// ``Err`` with the value read last if the condition has not been met within the given number of reads. The
+        /// value read last helps diagnosing the state the peripheral got stuck in.
+        ///
+        /// # Example
+        /// ```no_run
+        /// # use ruspiro_mmio_register::*;
+        /// define_mmio_register!(
+        ///     STATUS<ReadOnly<u32>@(0x3F20_0000)> {
+        ///         STATE OFFSET(0) BITS(3)
+        ///     }
+        /// );
+        ///
+        /// fn main() {
+        ///     match STATUS::Register.read_poll_timeout(STATUS::STATE, |state| state >= 4, 1000) {
+        ///         Ok(state) => { /* the peripheral is ready */ }
+        ///         Err(state) => panic!("peripheral stuck in state {}", state),
+        ///     }
+        /// }
+        /// ```
+        #[inline]
+        #[allow(dead_code)]
+        pub fn read_poll_timeout(
+            &self,
+            field: RegisterField<$t>,
+            cond: impl Fn($t) -> bool,
+            max_iters: usize,
+        ) -> Result<$t, $t> {
+            let mut value = self.read(field);
+            let mut iters = 1;
+            while !cond(value) {
+                if iters >= max_iters {
+                    return Err(value);
+                }
+                core::hint::spin_loop();
+                value = self.read(field);
+                iters += 1;
+            }
+            Ok(value)
+        }
+    };
+}
+
+macro_rules! registerset_impl {
+    ($t:ty) => {
+        /// Write raw content value to the register.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn set(&self, value: $t) {
+            self.backend.write(self.address(), value)
+        }
+
+        registerwrite_impl!($t);
+        registerwriteblock_impl!($t);
+    };
+}
+
+macro_rules! registersetbe_impl {
+    ($t:ty) => {
+        /// Write raw content value given in host endianness to the big-endian register.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn set(&self, value: $t) {
+            self.backend.write(self.address(), value.to_be())
+        }
+
+        registerwrite_impl!($t);
+        registerwriteblock_impl!($t);
+    };
+}
+
+macro_rules! registerwriteblock_impl {
+    ($t:ty) => {
+        /// Write each raw value of the slice to consecutive registers starting at this one. In contrast to
+        /// ``write_slice`` the address advances by the register width for each value, so this is meant to write a
+        /// memory mapped table, e.g. a mailbox buffer.
+        ///
+        /// # Panics
+        /// In debug builds this panics if the slice is empty.
+        ///
+        /// # Hint
+        /// The caller is responsible that all addresses written are valid registers of the same type.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn write_block(&self, data: &[$t]) {
+            debug_assert!(!data.is_empty(), "register block is empty");
+            for (index, value) in data.iter().enumerate() {
+                self.offset(index as isize).set(*value);
+            }
+        }
+    };
+}
+
+macro_rules! registerwrite_impl {
+    ($t:ty) => {
+        /// Write raw content value to the register with a compiler fence of the given ``Ordering`` before and after
+        /// the write. This prevents the compiler from moving memory accesses of the surrounding code across the
+        /// register write. ``Release`` is typically used when writing a register that starts the device processing
+        /// data prepared in memory, so the data is written before the device is started. ``SeqCst`` prevents any
+        /// reordering.
+        ///
+        /// # Panics
+        /// This panics if ``order`` is ``Relaxed``.
+        ///
+        /// # Hint
+        /// The fence does only restrict the compiler. Reordering by the CPU requires memory barriers.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn set_ordered(&self, value: $t, order: Ordering) {
+            compiler_fence(order);
+            self.set(value);
+            compiler_fence(order);
+        }
+
+        /// Write raw content value to the register with a data memory barrier before and a data synchronization
+        /// barrier after the write. This orders the write with the accesses to other peripherals before it and waits
+        /// for the write to complete.
+        #[cfg(feature = "arm-barriers")]
+        #[inline]
+        #[allow(dead_code)]
+        pub fn set_barrier(&self, value: $t) {
+            barrier::data_memory_barrier();
+            self.set(value);
+            barrier::data_sync_barrier();
+        }
+
+        /// Perform a sequence of accesses to the register, e.g. when configuring a peripheral, with a single data
+        /// memory barrier before and a single data synchronization barrier after them instead of barriers around each
+        /// access. The closure ``f`` receives this register to perform plain accesses.
+        ///
+        /// # Hint
+        /// The accesses within the closure are not ordered individually with accesses to other peripherals made
+        /// within the closure. Only the sequence as a whole is ordered with the accesses before and after it.
+        #[cfg(feature = "arm-barriers")]
+        #[inline]
+        #[allow(dead_code)]
+        pub fn transaction<F: FnOnce(&Self)>(&self, f: F) {
+            barrier::data_memory_barrier();
+            f(self);
+            barrier::data_sync_barrier();
+        }
+
+        /// Write the value of a specific register field, this will set all bits not coverd by this field to 0 !
+        /// To keep the other bits, e.g. reserved bits that must be preserved, use ``write_preserving`` of the
+        /// registers that can be read.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn write(&self, field: RegisterField<$t>, value: $t) {
+            let val = (value << field.shift()) & field.mask();
+            self.set(val);
+        }
+
+        /// Write the value of a specific register field like ``write``, but only if the value fits into the width of
+        /// the field. Otherwise the error containing the value and the width of the field is returned and the register
+        /// is not written.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn try_write(&self, field: RegisterField<$t>, value: $t) -> Result<(), ValueError<$t>> {
+            self.write_value(RegisterFieldValue::<$t>::try_new(field, value)?);
+            Ok(())
+        }
+
+        /// Write a signed value to a specific register field storing two's-complement values. The field of ``n`` bits
+        /// stores values in the range ``-2^(n-1)..=2^(n-1)-1``, e.g. ``-8..=7`` for a 4 bit field. Like ``write`` this
+        /// will set all bits not coverd by this field to 0 !
+        ///
+        /// # Panics
+        /// In debug builds this panics if the value is outside the range the field can store.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn write_signed(&self, field: RegisterField<$t>, value: i64) {
+            let bits = field.mask().count_ones();
+            debug_assert!(
+                bits >= 64 || (value >= -(1 << (bits - 1)) && value < (1 << (bits - 1))),
+                "value exceeds the range of the signed field"
+            );
+            self.write(field, value as $t);
+        }
+
+        /// Write a value to a register field storing an unsigned fixed-point value with ``frac_bits`` fractional
+        /// bits. The value is scaled by ``2^frac_bits`` and rounded to the nearest raw field value, where values
+        /// exactly halfway between two raw values are rounded up. Values exceeding the range of the field saturate:
+        /// negative values and NaN are written as 0, values above the largest value the field can store are written
+        /// as this largest value. Like ``write`` this will set all bits not coverd by this field to 0 !
+        ///
+        /// # Panics
+        /// This panics if ``frac_bits`` is 64 or more.
+        #[cfg(feature = "float")]
+        #[inline]
+        #[allow(dead_code)]
+        pub fn write_fixed(&self, field: RegisterField<$t>, value: f32, frac_bits: u32) {
+            assert!(frac_bits < 64, "fractional bits exceed 63");
+            let max = field.mask() >> field.shift();
+            // the conversion into the integer saturates at 0 for negative values and NaN
+            let scaled = value * (1u64 << frac_bits) as f32 + 0.5;
+            let raw = if scaled >= max as f32 { max } else { scaled as $t };
+            self.write(field, raw);
+        }
+
+        /// Write the value of a given RegisterFieldValue to the register, this will set all bits not coverd by this 
+        /// field to 0 !
+        #[inline]
+        #[allow(dead_code)]
+        pub fn write_value(&self, fieldvalue: RegisterFieldValue<$t>) {
+            self.set(fieldvalue.raw_value());
+        }
+
+        /// Write the values of several register fields with a single write of the register, e.g. to configure a write
+        /// only register that could not be updated with a read-modify-write. The field values are combined into one
+        /// raw value starting from 0, so all bits not coverd by any of the field values are set to 0 !
+        #[inline]
+        #[allow(dead_code)]
+        pub fn write_values(&self, fieldvalues: &[RegisterFieldValue<$t>]) {
+            let raw_val = fieldvalues
+                .iter()
+                .fold(0, |val, fieldvalue| val | (fieldvalue.raw_value() & fieldvalue.mask()));
+            self.set(raw_val);
+        }
+
+        /// Write the bits of a type defined with the ``bitflags`` crate to the register, this will set all bits not
+        /// contained in the flags to 0 ! The flags need to have the width of the register, so their ``Bits`` type has
+        /// to be the type of the register.
+        #[cfg(feature = "bitflags")]
+        #[inline]
+        #[allow(dead_code)]
+        pub fn write_flags<F: bitflags::Flags<Bits = $t>>(&self, flags: F) {
+            self.set(flags.bits());
+        }
+
+        /// Write the same raw value ``count`` times to the register. All writes go to the same register, so this is
+        /// meant to feed a hardware FIFO.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn write_repeated(&self, value: $t, count: usize) {
+            for _ in 0..count {
+                self.set(value);
+            }
+        }
+
+        /// Write each raw value of the slice to the register. All writes go to the same register, so this is meant to
+        /// feed a hardware FIFO.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn write_slice(&self, data: &[$t]) {
+            for value in data {
+                self.set(*value);
+            }
+        }
+
+        /// Clear all bits of the register by writing 0 to it.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn clear_all(&self) {
+            self.set(0);
+        }
+
+        /// Set all bits of the register by writing all ones to it.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn set_all(&self) {
+            self.set(!0);
+        }
+    };
+}
+
+/// Guard of the read-modify-write of a register. With the ``critical-section`` feature it enters a critical section
+/// that is left once the guard is dropped. This serializes the read-modify-writes on all cores using the same
+/// critical section implementation. Each read-modify-write of a register access type takes this guard, and the
+/// functions of the registers defined with [define_mmio_register] updating a register are based on them, e.g. on
+/// ``rmw`` of the [Modifiable] trait, instead of reading and writing the register on their own.
+#[cfg(feature = "critical-section")]
+struct RmwGuard(critical_section::RestoreState);
+
+#[cfg(feature = "critical-section")]
+impl RmwGuard {
+    #[inline]
+    fn new() -> Self {
+        Self(unsafe { critical_section::acquire() })
+    }
+}
+
+#[cfg(feature = "critical-section")]
+impl Drop for RmwGuard {
+    #[inline]
+    fn drop(&mut self) {
+        // the guards are dropped in the reverse order they are created, so nested critical sections are released
+        // in the order required
+        unsafe { critical_section::release(self.0) }
+    }
+}
+
+/// Guard of the read-modify-write of a register. Without the ``critical-section`` feature it does nothing.
+#[cfg(not(feature = "critical-section"))]
+struct RmwGuard;
+
+#[cfg(not(feature = "critical-section"))]
+impl RmwGuard {
+    #[inline]
+    fn new() -> Self {
+        Self
+    }
+}
+
+macro_rules! registermodify_impl {
+    ($t:ty) => {
+        registermodify_impl!($t, "the register is read first");
+    };
+    // the functions keeping bits of the register document how the current contents are obtained
+    ($t:ty, $read:literal) => {
+        /// Udate a register field with a given value. The bits outside of this field remains untouched.
+        /// The function returns the register raw value set has been set with this update
+        #[inline]
+        #[allow(dead_code)]
+        pub fn modify(&self, field: RegisterField<$t>, value: $t) -> $t {
+            let _guard = RmwGuard::new();
+            let old_val = self.get();
+            let raw_val = (value << field.shift()) & field.mask();
+            let new_val = (old_val & !field.mask()) | raw_val;
+
+            self.set(new_val);
+            new_val 
+        }
+
+        #[doc = concat!(
+            "Write the value of a specific register field while preserving all bits not covered by this field. In ",
+            "contrast to ``write`` ", $read, " and the other bits, like reserved bits the hardware requires to keep ",
+            "their value, are written back unchanged. This is the same as ``modify``."
+        )]
+        #[inline]
+        #[allow(dead_code)]
+        pub fn write_preserving(&self, field: RegisterField<$t>, value: $t) {
+            self.modify(field, value);
+        }
+
+        /// Udate a register field with a given register field value. The bits outside of this field remains 
+        /// untouched. The field value may combine the values of several fields with ``|``, then only the bits of
+        /// those fields are updated. A field combined with the value 0 is cleared.
+        /// The function returns the register raw value set has been set with this update
+        #[inline]
+        #[allow(dead_code)]
+        pub fn modify_value(&self, fieldvalue: RegisterFieldValue<$t>) -> $t {
+            let _guard = RmwGuard::new();
+            let old_val = self.get();
+            let raw_val = fieldvalue.raw_value() & fieldvalue.mask();
+            let new_val = (old_val & !fieldvalue.mask()) | raw_val;
+
+            self.set(new_val);
+            new_val
+        }
+
+        #[doc = concat!(
+            "Write the value of a given RegisterFieldValue while preserving all bits not covered by it. In contrast ",
+            "to ``write_value``, setting all other bits to 0, ", $read, " and only the bits of the fields combined in ",
+            "the field value are updated. This is the same as ``modify_value``."
+        )]
+        #[inline]
+        #[allow(dead_code)]
+        pub fn update_value(&self, fieldvalue: RegisterFieldValue<$t>) {
+            self.modify_value(fieldvalue);
+        }
+
+        /// Write raw content value to the register and return the raw value it contained before. This allows to save
+        /// and restore the register contents, e.g. for nested critical sections.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn replace(&self, value: $t) -> $t {
+            let _guard = RmwGuard::new();
+            let old_val = self.get();
+
+            self.set(value);
+            old_val
+        }
 
-pub use ruspiro_register::*;
-pub mod macros;
+        /// Udate a register field with a given value. The bits outside of this field remains untouched.
+        /// In contrast to ``modify`` the function returns the value the field contained before this update
+        #[inline]
+        #[allow(dead_code)]
+        pub fn modify_replace(&self, field: RegisterField<$t>, value: $t) -> $t {
+            let _guard = RmwGuard::new();
+            let old_val = self.get();
+            let raw_val = (value << field.shift()) & field.mask();
 
-/// This struct allows read only access to a register.
-#[derive(Clone, Debug)]
-pub struct ReadOnly<T: RegisterType> {
-    ptr: *mut T, // base address for the register
-}
+            self.set((old_val & !field.mask()) | raw_val);
+            (old_val & field.mask()) >> field.shift()
+        }
 
-/// This struct allows write only access to a register.
-#[derive(Clone, Debug)]
-pub struct WriteOnly<T: RegisterType> {
-    ptr: *mut T, // base address for the register
-}
+        /// Udate one or more register fields with a given register field value. The bits outside of those fields
+        /// remains untouched. In contrast to ``modify_value`` the function returns the register field value the
+        /// fields contained before this update. Passing it to ``modify_value`` later restores exactly those
+        /// contents, e.g. when leaving a nested configuration scope.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn replace_value(&self, fieldvalue: RegisterFieldValue<$t>) -> RegisterFieldValue<$t> {
+            let _guard = RmwGuard::new();
+            let old_val = self.get();
+            let raw_val = fieldvalue.raw_value() & fieldvalue.mask();
 
-/// This struct allows read/write access to a register.
-#[derive(Clone, Debug)]
-pub struct ReadWrite<T: RegisterType> {
-    ptr: *mut T, // base address for the register
-}
+            self.set((old_val & !fieldvalue.mask()) | raw_val);
+            // the mask covers all fields updated, so it is used as field without a shift
+            RegisterFieldValue::<$t>::new(RegisterField::<$t>::new(fieldvalue.mask(), 0), old_val)
+        }
 
-/*************** internal used macros to ease implementation ******************/
-macro_rules! registernew_impl {
-    ($t:ty) => {
-        /// Create a new instance of the register access struct.
+        /// Udate a register field with a given value, but only write the register if this changes its contents. The
+        /// bits outside of this field remains untouched. This avoids redundant writes, e.g. on slow peripheral buses.
+        /// The function returns whether the register has been written.
+        ///
+        /// # Hint
+        /// Registers where each write has a side effect, even if the value is unchanged, need to be updated with
+        /// ``modify`` instead.
+        #[inline]
         #[allow(dead_code)]
-        pub const fn new(addr: usize) -> Self {
-            Self {
-                ptr: addr as *mut $t,
+        pub fn modify_if_changed(&self, field: RegisterField<$t>, value: $t) -> bool {
+            let _guard = RmwGuard::new();
+            let old_val = self.get();
+            let raw_val = (value << field.shift()) & field.mask();
+            let new_val = (old_val & !field.mask()) | raw_val;
+
+            if new_val == old_val {
+                return false;
             }
+            self.set(new_val);
+            true
         }
-    };
-}
 
-macro_rules! registerget_impl {
-    ($t:ty) => {
-        /// Read raw content of a register.
+        /// Udate a register field with the value calculated from its current value by the given closure. The bits
+        /// outside of this field remains untouched. The value returned by the closure is truncated to the width of
+        /// the field. The function returns the register raw value set has been set with this update
         #[inline]
         #[allow(dead_code)]
-        pub fn get(&self) -> $t {
-            unsafe { read_volatile(self.ptr) }
+        pub fn modify_with<F: FnOnce($t) -> $t>(&self, field: RegisterField<$t>, f: F) -> $t {
+            let _guard = RmwGuard::new();
+            let old_val = self.get();
+            let raw_val = (f((old_val & field.mask()) >> field.shift()) << field.shift()) & field.mask();
+            let new_val = (old_val & !field.mask()) | raw_val;
+
+            self.set(new_val);
+            new_val
         }
 
-        /// Read the value of a specific register field
+        /// Udate the register with the raw value calculated from its current raw value by the given closure. This
+        /// is the most general read-modify-write, e.g. to implement a device specific update inline. The function
+        /// returns the register raw value before and after this update as ``(old, new)``, e.g. for logging or
+        /// verification.
+        ///
+        /// # Hint
+        /// The read and the write are separate accesses, so an update of the register by another core in between
+        /// is lost. Registers updated by several cores should use ``rmw`` of ``AtomicReadWrite`` instead.
+        ///
+        /// # Example
+        /// ```
+        /// # use ruspiro_mmio_register::*;
+        /// # use ruspiro_mmio_register::backend::Memory;
+        /// fn main() {
+        ///     let memory = Memory::<1>::new(0x1000);
+        ///     let register = ReadWrite::<u32, _>::with_backend(0x1000, &memory);
+        ///     register.set(0x0F);
+        ///
+        ///     let (old, new) = register.rmw(|value| (value << 4) | 0x1);
+        ///     assert_eq!((old, new), (0x0F, 0xF1));
+        ///     assert_eq!(register.get(), 0xF1);
+        /// }
+        /// ```
         #[inline]
         #[allow(dead_code)]
-        pub fn read(&self, field: RegisterField<$t>) -> $t {
-            let val = self.get();
-            (val & field.mask() ) >> field.shift() 
+        pub fn rmw(&self, f: impl FnOnce($t) -> $t) -> ($t, $t) {
+            let _guard = RmwGuard::new();
+            let old_val = self.get();
+            let new_val = f(old_val);
+
+            self.set(new_val);
+            (old_val, new_val)
         }
 
-        /// Read the value of the register into a RegisterFieldValue structure
+        /// Udate several register fields with a single read-modify-write of the register. The bits outside of those
+        /// fields remains untouched. If fields of the given values overlap, they are applied in the order given, so the
+        /// last one wins. The function returns the register raw value set has been set with this update
         #[inline]
         #[allow(dead_code)]
-        pub fn read_value(&self, field: RegisterField<$t>) -> RegisterFieldValue<$t> {
-            RegisterFieldValue::<$t>::new(field, self.read(field))
+        pub fn modify_fields(&self, fieldvalues: &[RegisterFieldValue<$t>]) -> $t {
+            let _guard = RmwGuard::new();
+            let new_val = fieldvalues.iter().fold(self.get(), |val, fieldvalue| {
+                (val & !fieldvalue.mask()) | (fieldvalue.raw_value() & fieldvalue.mask())
+            });
+
+            self.set(new_val);
+            new_val
         }
-    };
-}
 
-macro_rules! registerset_impl {
-    ($t:ty) => {
-        /// Write raw content value to the register.
+        /// Toggle all bits of a register field. The bits outside of this field remains untouched.
+        /// The function returns the register raw value set has been set with this update
         #[inline]
         #[allow(dead_code)]
-        pub fn set(&self, value: $t) {
-            unsafe { write_volatile(self.ptr, value) }
+        pub fn toggle(&self, field: RegisterField<$t>) -> $t {
+            let _guard = RmwGuard::new();
+            let new_val = self.get() ^ field.mask();
+
+            self.set(new_val);
+            new_val
         }
 
-        /// Write the value of a specific register field, this will set all bits not coverd by this field to 0 !
+        /// Invert all bits of the register.
+        /// The function returns the register raw value set has been set with this update
         #[inline]
         #[allow(dead_code)]
-        pub fn write(&self, field: RegisterField<$t>, value: $t) {
-            let val = (value << field.shift()) & field.mask();
-            self.set(val);
+        pub fn invert(&self) -> $t {
+            let _guard = RmwGuard::new();
+            let new_val = !self.get();
+
+            self.set(new_val);
+            new_val
         }
 
-        /// Write the value of a given RegisterFieldValue to the register, this will set all bits not coverd by this 
-        /// field to 0 !
+        /// Set the single bit ``n`` of the register to 1. The other bits remains untouched.
+        /// The function returns the register raw value set has been set with this update
+        ///
+        /// # Panics
+        /// In debug builds this panics if ``n`` exceeds the register width.
         #[inline]
         #[allow(dead_code)]
-        pub fn write_value(&self, fieldvalue: RegisterFieldValue<$t>) {
-            self.set(fieldvalue.raw_value());
+        pub fn set_bit(&self, n: u32) -> $t {
+            let _guard = RmwGuard::new();
+            debug_assert!(n < <$t>::BITS, "bit exceeds the register width");
+            let new_val = self.get() | (1 << n);
+
+            self.set(new_val);
+            new_val
+        }
+
+        /// Clear the single bit ``n`` of the register to 0. The other bits remains untouched.
+        /// The function returns the register raw value set has been set with this update
+        ///
+        /// # Panics
+        /// In debug builds this panics if ``n`` exceeds the register width.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn clear_bit(&self, n: u32) -> $t {
+            let _guard = RmwGuard::new();
+            debug_assert!(n < <$t>::BITS, "bit exceeds the register width");
+            let new_val = self.get() & !(1 << n);
+
+            self.set(new_val);
+            new_val
+        }
+
+        /// Set all bits of a register field to 1. The bits outside of this field remains untouched.
+        /// The function returns the register raw value set has been set with this update
+        #[inline]
+        #[allow(dead_code)]
+        pub fn set_bits(&self, field: RegisterField<$t>) -> $t {
+            let _guard = RmwGuard::new();
+            let new_val = self.get() | field.mask();
+
+            self.set(new_val);
+            new_val
+        }
+
+        /// Clear all bits of a register field to 0. The bits outside of this field remains untouched.
+        /// The function returns the register raw value set has been set with this update
+        #[inline]
+        #[allow(dead_code)]
+        pub fn clear_bits(&self, field: RegisterField<$t>) -> $t {
+            let _guard = RmwGuard::new();
+            let new_val = self.get() & !field.mask();
+
+            self.set(new_val);
+            new_val
+        }
+
+        /// Update the ``len`` bits of the register starting at bit ``offset`` with a given value. The bits outside of
+        /// this range remains untouched. The function returns the register raw value set has been set with this update
+        ///
+        /// # Panics
+        /// In debug builds this panics if the bits exceed the register width.
+        #[inline]
+        #[allow(dead_code)]
+        pub fn modify_bits(&self, offset: u32, len: u32, value: $t) -> $t {
+            let _guard = RmwGuard::new();
+            debug_assert!(offset + len <= <$t>::BITS, "bits exceed the register width");
+            let mask = (!(0 as $t))
+                .checked_shr(<$t>::BITS - len)
+                .unwrap_or(0)
+                .checked_shl(offset)
+                .unwrap_or(0);
+            let new_val = (self.get() & !mask) | (value.checked_shl(offset).unwrap_or(0) & mask);
+
+            self.set(new_val);
+            new_val
         }
     };
 }
 
+// The register widths are limited to the ones ``RegisterType``, ``RegisterField`` and ``RegisterFieldValue`` are
+// provided for by the ``ruspiro-register`` crate. ``u128`` is not supported: those can not be implemented for
+// additional widths outside of that crate, and the trace hook, the mocked address space and ``MmioAccess`` pass the
+// register values as ``u64``.
 macro_rules! readonly_impl {
     ($( $t:ty ),*) => { $(
         impl ReadOnly<$t> {
             registernew_impl!($t);
+
+            /// Create a new instance of the register access struct from a const pointer to the register, e.g. a
+            /// register located in read only memory. The register is only ever read through this pointer.
+            ///
+            /// # Panics
+            /// In debug builds this panics if ``ptr`` is not aligned to the register width.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn from_const_ptr(ptr: *const $t) -> Self {
+                Self::new(ptr as usize)
+            }
+        }
+        impl<B> ReadOnly<$t, B> {
+            registerbackend_impl!($t);
+        }
+        impl<B: MmioAccess> ReadOnly<$t, B> {
+            registerptr_impl!($t, const);
+            registeroffset_impl!($t);
+            registernarrow_impl!($t);
             registerget_impl!($t);
+
+            /// Upgrade the read only register to a read/write register at the same address using the same backend.
+            /// This is meant for registers documented as read only that are writeable on the silicon revision used,
+            /// which the caller vouches for with the [WriteOverride]. Returns ``None`` if the address is 0 or not
+            /// aligned to the register width, as writing to it would fault.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn try_as_read_write(self, _vouched: WriteOverride) -> Option<ReadWrite<$t, B>> {
+                let addr = self.address();
+                if addr == 0 || addr % core::mem::align_of::<$t>() != 0 {
+                    None
+                } else {
+                    Some(ReadWrite::<$t, B>::with_backend(addr, self.backend))
+                }
+            }
         }
+        sealed_impl!(ReadOnly, $t, B);
+        readable_impl!(ReadOnly, $t, B);
     )* };
 }
 readonly_impl![u8, u16, u32, u64];
@@ -155,45 +2305,510 @@ macro_rules! writeonly_impl {
     ($( $t:ty ),*) => { $(
         impl WriteOnly<$t> {
             registernew_impl!($t);
+        }
+        impl<B> WriteOnly<$t, B> {
+            registerbackend_impl!($t);
+        }
+        impl<B: MmioAccess> WriteOnly<$t, B> {
+            registerptr_impl!($t);
+            registeroffset_impl!($t);
+            registernarrow_impl!($t);
             registerset_impl!($t);
         }
+        sealed_impl!(WriteOnly, $t, B);
+        writeable_impl!(WriteOnly, $t, B);
     )* };
 }
 writeonly_impl![u8, u16, u32, u64];
 
+macro_rules! writeonlyshadow_impl {
+    ($( $t:ty ),*) => { $(
+        impl WriteOnlyShadow<$t> {
+            /// Create a new instance of the register access struct. The shadow copy is initialized with 0.
+            #[allow(dead_code)]
+            pub const fn new(addr: usize) -> Self {
+                Self::with_reset_value(addr, 0)
+            }
+
+            /// Create a new instance of the register access struct with the shadow copy initialized with the reset
+            /// value of the register.
+            ///
+            /// # Panics
+            /// In debug builds this panics if ``addr`` is not aligned to the register width.
+            #[allow(dead_code)]
+            pub const fn with_reset_value(addr: usize, reset_value: $t) -> Self {
+                debug_assert!(addr % core::mem::align_of::<$t>() == 0, "register address is not aligned");
+                Self {
+                    ptr: addr as *mut $t,
+                    shadow: Cell::new(reset_value),
+                }
+            }
+
+            registertrynew_impl!($t);
+            registerptr_impl!($t);
+
+            /// Provide the value last written to the register as kept in the shadow copy. This does not access the
+            /// register.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn shadow(&self) -> $t {
+                self.shadow.get()
+            }
+
+            /// Write raw content value to the register and keep it in the shadow copy.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn set(&self, value: $t) {
+                self.shadow.set(value);
+                mmio_write(self.ptr, value)
+            }
+
+            // the read-modify-write functions read the shadow copy instead of the register
+            #[inline]
+            fn get(&self) -> $t {
+                self.shadow.get()
+            }
+
+            registerwrite_impl!($t);
+            registermodify_impl!(
+                $t,
+                "the register is not read, but the value last written kept as shadow copy is used"
+            );
+        }
+        sealed_impl!(WriteOnlyShadow, $t);
+        writeable_impl!(WriteOnlyShadow, $t);
+    )* };
+}
+writeonlyshadow_impl![u8, u16, u32, u64];
+
 macro_rules! readwrite_impl {
     ($( $t:ty ),*) => { $(
         impl ReadWrite<$t> {
             registernew_impl!($t);
+        }
+        impl<B> ReadWrite<$t, B> {
+            registerbackend_impl!($t);
+        }
+        impl<B: MmioAccess> ReadWrite<$t, B> {
+            registerptr_impl!($t);
+            registeroffset_impl!($t);
+            registernarrow_impl!($t);
+            registerget_impl!($t);
+            registerset_impl!($t);
+            registerviews_impl!($t, B, ReadOnly, WriteOnly);
+            registermodify_impl!($t);
+        }
+        sealed_impl!(ReadWrite, $t, B);
+        readable_impl!(ReadWrite, $t, B);
+        writeable_impl!(ReadWrite, $t, B);
+        modifiable_impl!(ReadWrite, $t, B);
+    )* };
+}
+readwrite_impl![u8, u16, u32, u64];
+
+macro_rules! readonlybe_impl {
+    ($( $t:ty ),*) => { $(
+        impl ReadOnlyBE<$t> {
+            registernew_impl!($t);
+        }
+        impl<B> ReadOnlyBE<$t, B> {
+            registerbackend_impl!($t);
+        }
+        impl<B: MmioAccess> ReadOnlyBE<$t, B> {
+            registerptr_impl!($t, const);
+            registeroffset_impl!($t);
+            registernarrow_impl!($t);
+            registergetbe_impl!($t);
+        }
+        sealed_impl!(ReadOnlyBE, $t, B);
+        readable_impl!(ReadOnlyBE, $t, B);
+    )* };
+}
+readonlybe_impl![u8, u16, u32, u64];
+
+macro_rules! writeonlybe_impl {
+    ($( $t:ty ),*) => { $(
+        impl WriteOnlyBE<$t> {
+            registernew_impl!($t);
+        }
+        impl<B> WriteOnlyBE<$t, B> {
+            registerbackend_impl!($t);
+        }
+        impl<B: MmioAccess> WriteOnlyBE<$t, B> {
+            registerptr_impl!($t);
+            registeroffset_impl!($t);
+            registernarrow_impl!($t);
+            registersetbe_impl!($t);
+        }
+        sealed_impl!(WriteOnlyBE, $t, B);
+        writeable_impl!(WriteOnlyBE, $t, B);
+    )* };
+}
+writeonlybe_impl![u8, u16, u32, u64];
+
+macro_rules! readwritebe_impl {
+    ($( $t:ty ),*) => { $(
+        impl ReadWriteBE<$t> {
+            registernew_impl!($t);
+        }
+        impl<B> ReadWriteBE<$t, B> {
+            registerbackend_impl!($t);
+        }
+        impl<B: MmioAccess> ReadWriteBE<$t, B> {
+            registerptr_impl!($t);
+            registeroffset_impl!($t);
+            registernarrow_impl!($t);
+            registergetbe_impl!($t);
+            registersetbe_impl!($t);
+            registerviews_impl!($t, B, ReadOnlyBE, WriteOnlyBE);
+            registermodify_impl!($t);
+        }
+        sealed_impl!(ReadWriteBE, $t, B);
+        readable_impl!(ReadWriteBE, $t, B);
+        writeable_impl!(ReadWriteBE, $t, B);
+        modifiable_impl!(ReadWriteBE, $t, B);
+    )* };
+}
+readwritebe_impl![u8, u16, u32, u64];
+
+macro_rules! atomicreadwrite_impl {
+    ($( $t:ty => $atomic:ty ),*) => { $(
+        impl AtomicReadWrite<$t> {
+            registernew_impl!($t);
+            registerptr_impl!($t);
+            registeroffset_impl!($t);
+            registernarrow_impl!($t);
             registerget_impl!($t);
             registerset_impl!($t);
+            registerviews_impl!($t, HardwareMmio, ReadOnly, WriteOnly);
 
-            /// Udate a register field with a given value. The bits outside of this field remains untouched.
-            /// The function returns the register raw value set has been set with this update
+            /// Atomically update a register field with a given value. The bits outside of this field remains 
+            /// untouched. The function returns the register raw value set has been set with this update
             #[inline]
             #[allow(dead_code)]
             pub fn modify(&self, field: RegisterField<$t>, value: $t) -> $t {
-                let old_val = self.get();
-                let raw_val = (value << field.shift()) & field.mask();
-                let new_val = (old_val & !field.mask()) | raw_val;
+                self.modify_ordered(field, value, Ordering::SeqCst)
+            }
 
-                self.set(new_val);
-                new_val 
+            /// Atomically write the value of a specific register field while preserving all bits not covered by this
+            /// field. In contrast to ``write`` the other bits, like reserved bits the hardware requires to keep their
+            /// value, are written back unchanged. This is the same as ``modify``.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn write_preserving(&self, field: RegisterField<$t>, value: $t) {
+                self.modify(field, value);
             }
 
-            /// Udate a register field with a given register field value. The bits outside of this field remains 
-            /// untouched. The function returns the register raw value set has been set with this update
+            /// Atomically update a register field with a given register field value. The bits outside of this field
+            /// remains untouched. The function returns the register raw value set has been set with this update
             #[inline]
             #[allow(dead_code)]
             pub fn modify_value(&self, fieldvalue: RegisterFieldValue<$t>) -> $t {
-                let old_val = self.get();
                 let raw_val = fieldvalue.raw_value() & fieldvalue.mask();
-                let new_val = (old_val & !fieldvalue.mask()) | raw_val;
+                self.update(fieldvalue.mask(), raw_val, Ordering::SeqCst)
+            }
+
+            /// Atomically write the value of a given RegisterFieldValue while preserving all bits not covered by it.
+            /// In contrast to ``write_value`` only the bits of the fields combined in the field value are updated.
+            /// This is the same as ``modify_value``.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn update_value(&self, fieldvalue: RegisterFieldValue<$t>) {
+                self.modify_value(fieldvalue);
+            }
+
+            /// Atomically update one or more register fields with a given register field value. The bits outside of
+            /// those fields remains untouched. The function returns the register field value the fields contained
+            /// before this update, to restore them later with ``modify_value``.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn replace_value(&self, fieldvalue: RegisterFieldValue<$t>) -> RegisterFieldValue<$t> {
+                let raw_val = fieldvalue.raw_value() & fieldvalue.mask();
+                let old_val = self.fetch(|old_val| (old_val & !fieldvalue.mask()) | raw_val, Ordering::SeqCst);
+                RegisterFieldValue::<$t>::new(RegisterField::<$t>::new(fieldvalue.mask(), 0), old_val)
+            }
+
+            /// Atomically update a register field with a given value using the memory ``Ordering`` provided. The 
+            /// ordering is the one applied to the successful store of the new value. The function returns the
+            /// register raw value set has been set with this update
+            #[inline]
+            #[allow(dead_code)]
+            pub fn modify_ordered(&self, field: RegisterField<$t>, value: $t, order: Ordering) -> $t {
+                let raw_val = (value << field.shift()) & field.mask();
+                self.update(field.mask(), raw_val, order)
+            }
+
+            /// Atomically update the register with the raw value calculated from its current raw value by the given
+            /// closure. If the register is changed by another core in between, the closure is called again with the
+            /// new contents, so it should not have side effects. The function returns the register raw value before
+            /// and after this update as ``(old, new)``.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn rmw(&self, f: impl Fn($t) -> $t) -> ($t, $t) {
+                let old_val = self.fetch(&f, Ordering::SeqCst);
+                (old_val, f(old_val))
+            }
 
-                self.set(new_val);
-                new_val
+            #[inline]
+            fn update(&self, mask: $t, raw_val: $t, order: Ordering) -> $t {
+                let old_val = self.fetch(|old_val| (old_val & !mask) | raw_val, order);
+                (old_val & !mask) | raw_val
+            }
+
+            #[cfg(not(feature = "mock"))]
+            #[inline]
+            fn fetch<F: Fn($t) -> $t>(&self, f: F, order: Ordering) -> $t {
+                // the atomic types share the memory layout with their primitive counterpart
+                let atomic = unsafe { &*(self.ptr as *const $atomic) };
+                let fetch_order = match order {
+                    Ordering::Release => Ordering::Relaxed,
+                    Ordering::AcqRel => Ordering::Acquire,
+                    order => order,
+                };
+                let old_val = atomic
+                    .fetch_update(order, fetch_order, |old_val| Some(f(old_val)))
+                    .unwrap_or_else(|old_val| old_val);
+
+                trace_read(self.ptr as usize, old_val);
+                trace_write(self.ptr as usize, f(old_val));
+                old_val
+            }
+
+            #[cfg(feature = "mock")]
+            #[inline]
+            fn fetch<F: Fn($t) -> $t>(&self, f: F, _order: Ordering) -> $t {
+                let old_val = mock::fetch_update::<$t>(self.ptr as usize, &f);
+
+                trace_read(self.ptr as usize, old_val);
+                trace_write(self.ptr as usize, f(old_val));
+                old_val
             }
         }
+        sealed_impl!(AtomicReadWrite, $t);
+        readable_impl!(AtomicReadWrite, $t);
+        writeable_impl!(AtomicReadWrite, $t);
+        modifiable_impl!(AtomicReadWrite, $t);
     )* };
 }
-readwrite_impl![u8, u16, u32, u64];
+atomicreadwrite_impl![
+    u8 => core::sync::atomic::AtomicU8,
+    u16 => core::sync::atomic::AtomicU16,
+    u32 => core::sync::atomic::AtomicU32,
+    u64 => core::sync::atomic::AtomicU64
+];
+
+// The fetch operations on the whole register are performed with the compare-and-swap loop of ``fetch`` like the other
+// atomic updates, so they have the same hardware requirements. They are provided on 64 bit targets only.
+macro_rules! atomicfetch_impl {
+    ($( $t:ty ),*) => { $(
+        impl AtomicReadWrite<$t> {
+            /// Atomically apply a logical OR with the given value to the register using the memory ``Ordering``
+            /// provided. The function returns the register raw value before this update
+            #[inline]
+            #[allow(dead_code)]
+            pub fn fetch_or(&self, value: $t, order: Ordering) -> $t {
+                self.fetch(|old_val| old_val | value, order)
+            }
+
+            /// Atomically apply a logical AND with the given value to the register using the memory ``Ordering``
+            /// provided. The function returns the register raw value before this update
+            #[inline]
+            #[allow(dead_code)]
+            pub fn fetch_and(&self, value: $t, order: Ordering) -> $t {
+                self.fetch(|old_val| old_val & value, order)
+            }
+
+            /// Atomically apply a logical XOR with the given value to the register using the memory ``Ordering``
+            /// provided. The function returns the register raw value before this update
+            #[inline]
+            #[allow(dead_code)]
+            pub fn fetch_xor(&self, value: $t, order: Ordering) -> $t {
+                self.fetch(|old_val| old_val ^ value, order)
+            }
+        }
+    )* };
+}
+#[cfg(target_pointer_width = "64")]
+atomicfetch_impl![u32, u64];
+
+// The split register is provided for 64 bit registers only, as accessed in two 32 bit halves.
+macro_rules! split64_impl {
+    ($( $t:ty ),*) => { $(
+        impl Split64<$t> {
+            /// Create a new instance of the register access struct accessing the lower half first.
+            #[allow(dead_code)]
+            pub const fn new(addr: usize) -> Self {
+                Self::with_order(addr, SplitOrder::LowFirst)
+            }
+
+            /// Create a new instance of the register access struct accessing the halves in the ``order`` given.
+            ///
+            /// # Panics
+            /// In debug builds this panics if ``addr`` is not aligned to the width of the halves.
+            #[allow(dead_code)]
+            pub const fn with_order(addr: usize, order: SplitOrder) -> Self {
+                Self::with_backend_order(addr, HardwareMmio, order)
+            }
+        }
+
+        impl<B> Split64<$t, B> {
+            /// Create a new instance of the register access struct accessing the register through the ``backend``
+            /// given instead of the memory mapped I/O address. The lower half is accessed first.
+            #[allow(dead_code)]
+            pub const fn with_backend(addr: usize, backend: B) -> Self {
+                Self::with_backend_order(addr, backend, SplitOrder::LowFirst)
+            }
+
+            /// Create a new instance of the register access struct accessing the register through the ``backend``
+            /// given in the ``order`` given.
+            ///
+            /// # Panics
+            /// In debug builds this panics if ``addr`` is not aligned to the width of the halves.
+            #[allow(dead_code)]
+            pub const fn with_backend_order(addr: usize, backend: B, order: SplitOrder) -> Self {
+                debug_assert!(addr % core::mem::align_of::<u32>() == 0, "register address is not aligned");
+                Self {
+                    ptr: addr as *mut $t,
+                    backend,
+                    order,
+                }
+            }
+        }
+
+        impl<B: MmioAccess> Split64<$t, B> {
+            registerptr_impl!($t);
+            registernarrow_impl!($t);
+
+            /// Provide an accessor of the same type to the register ``count`` registers away from this one, so the
+            /// address is ``count * 8`` bytes away. This does not access the register.
+            ///
+            /// # Hint
+            /// The caller is responsible that the resulting address is a valid register of the same type.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn offset(&self, count: isize) -> Self {
+                Self {
+                    ptr: self.ptr.wrapping_offset(count),
+                    backend: self.backend.clone(),
+                    order: self.order,
+                }
+            }
+
+            /// Provide the order the halves of the register are accessed in.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn order(&self) -> SplitOrder {
+                self.order
+            }
+
+            /// Read raw content of the register with two 32 bit reads of its halves.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn get(&self) -> $t {
+                let (lo, hi) = match self.order {
+                    SplitOrder::LowFirst => {
+                        let lo = self.get_half(0);
+                        (lo, self.get_half(1))
+                    }
+                    SplitOrder::HighFirst => {
+                        let hi = self.get_half(1);
+                        (self.get_half(0), hi)
+                    }
+                };
+                (<$t>::from(hi) << 32) | <$t>::from(lo)
+            }
+
+            /// Write raw content value to the register with two 32 bit writes of its halves.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn set(&self, value: $t) {
+                let (lo, hi) = (value as u32, (value >> 32) as u32);
+                match self.order {
+                    SplitOrder::LowFirst => {
+                        self.set_half(0, lo);
+                        self.set_half(1, hi);
+                    }
+                    SplitOrder::HighFirst => {
+                        self.set_half(1, hi);
+                        self.set_half(0, lo);
+                    }
+                }
+            }
+
+            #[inline]
+            fn get_half(&self, half: usize) -> u32 {
+                self.backend.read(self.address() + half * 4)
+            }
+
+            #[inline]
+            fn set_half(&self, half: usize, value: u32) {
+                self.backend.write(self.address() + half * 4, value)
+            }
+
+            registerread_impl!($t);
+            registerwrite_impl!($t);
+            registerwriteblock_impl!($t);
+            registermodify_impl!($t);
+        }
+        sealed_impl!(Split64, $t, B);
+        readable_impl!(Split64, $t, B);
+        writeable_impl!(Split64, $t, B);
+        modifiable_impl!(Split64, $t, B);
+    )* };
+}
+split64_impl![u64];
+
+reg_impl![ReadOnly, u8, u16, u32, u64];
+reg_impl![WriteOnly, u8, u16, u32, u64];
+reg_impl![ReadWrite, u8, u16, u32, u64];
+reg_impl![ReadOnlyBE, u8, u16, u32, u64];
+reg_impl![WriteOnlyBE, u8, u16, u32, u64];
+reg_impl![ReadWriteBE, u8, u16, u32, u64];
+reg_impl![AtomicReadWrite, u8, u16, u32, u64];
+reg_impl![Split64, u64];
+
+impl<A: sealed::Sealed, const ADDR: usize> sealed::Sealed for Reg<A, ADDR> {}
+
+impl<A: Readable, const ADDR: usize> Readable for Reg<A, ADDR>
+where
+    Self: Deref<Target = A>,
+{
+    type Width = A::Width;
+
+    #[inline]
+    fn get(&self) -> A::Width {
+        (**self).get()
+    }
+}
+
+impl<A: Writeable, const ADDR: usize> Writeable for Reg<A, ADDR>
+where
+    Self: Deref<Target = A>,
+{
+    type Width = A::Width;
+
+    #[inline]
+    fn set(&self, value: A::Width) {
+        (**self).set(value)
+    }
+}
+
+impl<A: Modifiable, const ADDR: usize> Modifiable for Reg<A, ADDR>
+where
+    Self: Deref<Target = A>,
+{
+    #[inline]
+    fn modify(&self, field: RegisterField<A::Width>, value: A::Width) -> A::Width {
+        (**self).modify(field, value)
+    }
+
+    #[inline]
+    fn replace_value(&self, fieldvalue: RegisterFieldValue<A::Width>) -> RegisterFieldValue<A::Width> {
+        (**self).replace_value(fieldvalue)
+    }
+
+    #[inline]
+    fn rmw<F: Fn(A::Width) -> A::Width>(&self, f: F) -> (A::Width, A::Width) {
+        (**self).rmw(f)
+    }
+}
@@ -11,7 +11,9 @@
 //!
 
 /// Macro to define a MMIO register with specific defined access mode.<br>
-/// The access mode could one of: **ReadOnly**, **WriteOnly**, **ReadWrite**.<br>
+/// The access mode could one of: **ReadOnly**, **WriteOnly**, **ReadWrite**, **AtomicReadWrite**. Registers storing
+/// their value in big-endian byte order use **ReadOnlyBE**, **WriteOnlyBE** or **ReadWriteBE**. A **u64** register on
+/// a peripheral bus supporting 32 bit accesses only uses **Split64**.<br>
 /// The register size/width could be one of: **u8**, **u16**, **u32**, **u64**
 ///
 /// # Examples
@@ -45,100 +47,2506 @@
 /// );
 /// ```
 ///
+/// The fields could also be defined with the inclusive range of bits they cover, as given in most datasheets.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         // the same as BAR OFFSET(4) BITS(4)
+///         BAR BITS(4..=7)
+///     }
+/// );
+/// ```
+///
+/// Define a MMIO register where the read-modify-write of the ``modify`` functions is performed atomically. This does
+/// only work if the hardware supports atomic accesses to this memory region.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<AtomicReadWrite<u32>@(0x3F20_0000)> {
+///         BAR OFFSET(0),
+///         BAZ OFFSET(3) BITS(3)
+///     }
+/// );
+///
+/// fn main() {
+///     FOO::Register.modify(FOO::BAZ, 0b101);
+/// }
+/// ```
+///
+/// Define a MMIO register with write-1-to-clear fields by adding ``W1C`` to the field definition. Writing 1 to the
+/// bits of such a field clears them, while writing 0 has no effect. Those fields are cleared with the ``clear``
+/// function of the register, that does only write to the bits of the field given.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     IRQ_STATUS<ReadWrite<u32>@(0x3F00_B200)> {
+///         TIMER OFFSET(0) W1C,
+///         UART OFFSET(1) W1C,
+///         PENDING OFFSET(8) BITS(8)
+///     }
+/// );
+///
+/// fn main() {
+///     if IRQ_STATUS::Register.is_set(IRQ_STATUS::TIMER) {
+///         // acknowledge the timer interrupt without touching a pending UART interrupt
+///         IRQ_STATUS::Register.clear(IRQ_STATUS::TIMER);
+///     }
+/// }
+/// ```
+///
+/// Fields that are cleared when the register is read are marked with ``RC``. Their value is read with the
+/// ``read_and_clear`` function of the register, that makes the side effect of the read explicit.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FIFO_STATUS<ReadOnly<u32>@(0x3F00_C000)> {
+///         LEVEL OFFSET(0) BITS(4),
+///         OVERRUN OFFSET(4) RC
+///     }
+/// );
+///
+/// fn main() {
+///     if FIFO_STATUS::Register.read_and_clear(FIFO_STATUS::OVERRUN) == 1 {
+///         // handle the data lost
+///     }
+/// }
+/// ```
+///
+/// Reserved bits that need to keep their value when the register is written are declared as fields marked with
+/// ``RESERVED``. The ``write_config`` function of the register writes the given field values like ``write_value``,
+/// but preserves the contents of the reserved fields. In contrast, ``write`` sets all bits outside of the field
+/// written to 0 and ``write_preserving`` keeps all bits outside of the field written. ``modify_safe`` updates a single
+/// field like ``modify`` but rejects fields covering reserved bits.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     CONTROL<ReadWrite<u32>@(0x3F00_D000)> {
+///         ENABLE OFFSET(0),
+///         MODE OFFSET(1) BITS(2),
+///         RES0 OFFSET(3) BITS(5) RESERVED,
+///         DIVIDER OFFSET(8) BITS(8)
+///     }
+/// );
+///
+/// fn main() {
+///     CONTROL::Register.write_config(CONTROL::ENABLE::with_value(1) | CONTROL::DIVIDER::with_value(0x20));
+///     CONTROL::Register.write_preserving(CONTROL::MODE, 0b10);
+///     // panics if the field given covers bits of a reserved field
+///     CONTROL::Register.modify_safe(CONTROL::DIVIDER, 0x40);
+/// }
+/// ```
+///
+/// Fields where the hardware defines each encoding are marked with ``EXHAUSTIVE``. The register then fails to compile
+/// if the specific values of the field do not name each of its encodings exactly once, naming the encoding missing.
+/// ```compile_fail
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     CLOCK<ReadWrite<u32>@(0x3F00_E000)> {
+///         SOURCE OFFSET(6) BITS(2) EXHAUSTIVE [
+///             OSC = 0b00,
+///             PLLA = 0b01,
+///             PLLC = 0b11
+///         ]
+///     }
+/// );
+///
+/// fn main() {}
+/// ```
+///
+/// Fields marked with ``TYPED`` provide their value as a type of its own, named after the field like ``DiviValue``
+/// for the field ``DIVI``. It is read and updated with ``read_typed`` and ``modify_typed`` of the register. This
+/// prevents values of different fields of the same width from being mixed up.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     CM_DIV<ReadWrite<u32>@(0x3F10_1074)> {
+///         DIVI OFFSET(12) BITS(12) TYPED,
+///         DIVF OFFSET(0) BITS(12) TYPED
+///     }
+/// );
+///
+/// fn set_divider(integer: CM_DIV::DiviValue, fraction: CM_DIV::DivfValue) {
+///     CM_DIV::Register.modify_typed(integer);
+///     CM_DIV::Register.modify_typed(fraction);
+/// }
+///
+/// fn main() {
+///     let integer: CM_DIV::DiviValue = CM_DIV::Register.read_typed();
+///     set_divider(CM_DIV::DiviValue(*integer + 1), CM_DIV::DivfValue(0x800));
+/// }
+/// ```
+///
+/// Passing the values of the fields in the wrong order fails to compile:
+/// ```compile_fail
+/// # use ruspiro_mmio_register::*;
+/// # define_mmio_register!(
+/// #     CM_DIV<ReadWrite<u32>@(0x3F10_1074)> {
+/// #         DIVI OFFSET(12) BITS(12) TYPED,
+/// #         DIVF OFFSET(0) BITS(12) TYPED
+/// #     }
+/// # );
+/// # fn set_divider(integer: CM_DIV::DiviValue, fraction: CM_DIV::DivfValue) {}
+/// fn main() {
+///     let integer: CM_DIV::DiviValue = CM_DIV::Register.read_typed();
+///     let fraction: CM_DIV::DivfValue = CM_DIV::Register.read_typed();
+///     set_divider(fraction, integer);
+/// }
+/// ```
+///
+/// The register provides the decoded contents of its fields as debug output. Fields with specific values defined
+/// show the name of the value that matches the current contents.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         BAR OFFSET(0) BITS(3),
+///         BAL OFFSET(4) BITS(2) [
+///             VAL1 = 0b01,
+///             VAL2 = 0b10
+///         ]
+///     }
+/// );
+///
+/// fn main() {
+///     FOO::Register.set(0b10_0101);
+///     // prints FOO { BAR: 0b101, BAL: VAL2 }
+///     println!("{:?}", FOO::Register);
+/// }
+/// ```
+///
+/// Fields without a specific value matching their contents show their raw value, fields of up to 4 bits in binary and
+/// wider fields in hexadecimal notation with upper case digits. To match the notation of the datasheet, the notation
+/// of a field could be given with the ``#[fmt(hex)]`` or ``#[fmt(bin)]`` attribute of the field.
+/// ```
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         BAR OFFSET(0) BITS(3),
+///         #[fmt(hex)]
+///         BAZ OFFSET(3) BITS(5),
+///         #[fmt(bin)]
+///         BAL OFFSET(8) BITS(6)
+///     }
+/// );
+///
+/// fn main() {
+///     let values = FOO::Values::from_raw(0b10_1010_1111_1101);
+///     assert_eq!(format!("{:?}", values), "FOO { BAR: 0b101, BAZ: 0x1F, BAL: 0b101010 }");
+///     assert_eq!(format!("{}", values), "FOO { BAR=0b101, BAZ=0x1F, BAL=0b101010 }");
+/// }
+/// ```
+///
+/// The ``Values`` snapshot also renders the fields as a single compact line with ``Display``, e.g. for a log line.
+/// Fields without a specific value matching their contents show the raw value in the same notation as with ``Debug``.
+/// ```
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         BAR OFFSET(0) BITS(3),
+///         BAZ OFFSET(3) BITS(1) [
+///             VAL1 = 1
+///         ],
+///         BAL OFFSET(4) BITS(2)
+///     }
+/// );
+///
+/// fn main() {
+///     let values = FOO::Values::from_raw(0b10_1011);
+///     assert_eq!(format!("{}", values), "FOO { BAR=0b11, BAZ=VAL1, BAL=0b10 }");
+/// }
+/// ```
+///
+/// Several fields are read consistently with a single read of the register into a ``Values`` snapshot. The snapshot
+/// provides the value of each field with a function named like the field in lower case.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadOnly<u32>@(0x3F20_0000)> {
+///         BUSY OFFSET(0),
+///         LEVEL OFFSET(4) BITS(4)
+///     }
+/// );
+///
+/// fn main() {
+///     let values = FOO::Register.read_all();
+///     if values.busy() == 0 {
+///         let level = values.level();
+///     }
+/// }
+/// ```
+///
+/// The snapshot could also be changed with the ``with_`` functions of each field and written to the register with a
+/// single write. Updating several fields with a single read and write is done with ``modify_all``.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000) = 0x0> {
+///         ENABLE OFFSET(0),
+///         MODE OFFSET(4) BITS(4)
+///     }
+/// );
+///
+/// fn main() {
+///     FOO::Register.modify_all(|values| values.with_enable(1).with_mode(0b10));
+///     // write the fields without the current contents of the register
+///     FOO::Values::reset().with_mode(0b11).write(&FOO::Register);
+/// }
+/// ```
+///
+/// Instead of starting from the current contents, the builder of the register composes the contents field by field
+/// starting from the reset value of the register, or 0 if there is no reset value. The fields with specific values
+/// are set with their ``Value``. The builder either writes the composed contents to the register or updates only the
+/// fields set with a read-modify-write.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000) = 0x8000_0000> {
+///         BAR OFFSET(0) BITS(4),
+///         BAZ OFFSET(4) BITS(2) [
+///             VAL1 = 0b01,
+///             VAL2 = 0b10
+///         ]
+///     }
+/// );
+///
+/// fn main() {
+///     FOO::Register.build().bar(3).baz(FOO::BAZ::Value::VAL1).write();
+///     FOO::Register.build().baz(FOO::BAZ::Value::VAL2).modify();
+/// }
+/// ```
+///
+/// Two snapshots are compared with ``changed_fields`` providing the mask of the bits that differ, while the debug
+/// output of ``diff_debug`` shows only the fields that have changed.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadOnly<u32>@(0x3F20_0000)> {
+///         BUSY OFFSET(0),
+///         LEVEL OFFSET(4) BITS(4)
+///     }
+/// );
+///
+/// fn main() {
+///     let before = FOO::Register.read_all();
+///     // ... access the peripheral
+///     let after = FOO::Register.read_all();
+///     if before.changed_fields(&after) != 0 {
+///         // prints e.g. FOO { LEVEL: 0b10 -> 0b11 }
+///         println!("{:?}", before.diff_debug(&after));
+///     }
+/// }
+/// ```
+///
+/// The fields are public by default. A visibility given before the field name restricts the access to the field,
+/// e.g. to keep fields only used within the crate defining the register out of its public API.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     pub FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         ENABLE OFFSET(0),
+///         pub(crate) CALIBRATION OFFSET(4) BITS(4)
+///     }
+/// );
+///
+/// fn main() {
+///     FOO::Register.modify(FOO::CALIBRATION, 0b1010);
+/// }
+/// ```
+///
+/// A field that is known by different names, e.g. in different revisions of the datasheet, is defined once and
+/// referred to by the other names with ``ALIAS_OF(FIELD)``. The alias provides the same field and specific values.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         TXE OFFSET(0),
+///         TX_EMPTY ALIAS_OF(TXE)
+///     }
+/// );
+///
+/// fn main() {
+///     FOO::Register.modify(FOO::TX_EMPTY, 1);
+///     assert_eq!(FOO::Register.read(FOO::TXE), 1);
+/// }
+/// ```
+///
+/// A field that is no longer meant to be used, e.g. as it became reserved in a later revision of the chip, is marked
+/// with a ``#[deprecated]`` attribute. The register definition still compiles without warnings, while each use of
+/// the field, its specific values and the functions named after it emits the deprecation warning.
+/// ```compile_fail
+/// #![deny(deprecated)]
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         SPEED OFFSET(0) BITS(4),
+///         #[deprecated = "reserved since revision B, use SPEED instead"]
+///         CLKDIV OFFSET(4) BITS(4)
+///     }
+/// );
+///
+/// fn main() {
+///     FOO::Register.modify(FOO::CLKDIV, 0b10);
+/// }
+/// ```
+///
+/// A field that only exists on some chips is given with a ``#[cfg]`` attribute. If it does not hold, the field is
+/// compiled out together with all code generated for the register referring to it, e.g. the debug output. This way
+/// a single register definition serves all chips, even if the fields of different chips cover the same bits.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         ENABLE OFFSET(0),
+///         #[cfg(feature = "bcm2711")]
+///         DIV OFFSET(4) BITS(4),
+///         #[cfg(not(feature = "bcm2711"))]
+///         DIV OFFSET(4) BITS(2)
+///     }
+/// );
+///
+/// fn main() {
+///     FOO::Register.modify(FOO::DIV, 0b10);
+///     println!("{:?}", FOO::Register);
+/// }
+/// ```
+///
+/// Using a field whose ``cfg`` attribute does not hold fails to compile.
+/// ```compile_fail
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         ENABLE OFFSET(0),
+///         #[cfg(feature = "bcm2711")]
+///         PWM_MODE OFFSET(4) BITS(2)
+///     }
+/// );
+///
+/// fn main() {
+///     FOO::Register.modify(FOO::PWM_MODE, 0b10);
+/// }
+/// ```
+///
+/// The ``addr`` function of the register module provides the address of the register. As ``const fn`` it could be
+/// used in const context, e.g. to build a static table of register addresses. Registers relative to a base address
+/// only known at runtime do not provide it.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FIFO_A<WriteOnly<u32>@(0x3F20_4000)>,
+///     FIFO_B<WriteOnly<u32>@(0x3F20_4004)>
+/// );
+///
+/// static DMA_TARGETS: [usize; 2] = [FIFO_A::addr(), FIFO_B::addr()];
+///
+/// fn main() {
+///     assert_eq!(DMA_TARGETS[1], FIFO_B::Register.address());
+/// }
+/// ```
+///
+/// Define a MMIO register with the value it contains after a reset of the device. The value is provided as the
+/// ``RESET`` constant and restored with the ``reset`` function of the register. The ``Default`` of the register
+/// snapshot ``Values`` is the reset value as well, or 0 if there is no reset value. This allows to program the
+/// register from a clean baseline instead of its current contents.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000) = 0x0000_0180> {
+///         BAR OFFSET(7) BITS(2),
+///         BAZ OFFSET(0) BITS(4)
+///     }
+/// );
+///
+/// fn main() {
+///     FOO::Register.modify(FOO::BAR, 0b01);
+///     // restore the reset value 0x180
+///     FOO::Register.reset();
+///     // start from the reset value, set a field and write it
+///     FOO::Values::default().with_baz(0b1010).write(&FOO::Register);
+/// }
+/// ```
+///
+/// Define a MMIO register with ``@const`` in front of the address to keep the address in the type of the register
+/// instead of storing it within the register. This makes the register a zero sized type, that could be passed around
+/// for free.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@const(0x3F20_0000)> {
+///         BAR OFFSET(0)
+///     }
+/// );
+///
+/// fn main() {
+///     assert_eq!(core::mem::size_of_val(&FOO::Register), 0);
+///     FOO::Register.modify(FOO::BAR, 1);
+/// }
+/// ```
+///
+/// Define a MMIO register relative to a peripheral base address that is only known at runtime, e.g. as the MMU maps
+/// the peripherals to a different virtual address. The base address is a [PeripheralBase] and the register is given
+/// with ``@dyn(BASE + OFFSET)``. The ``Register`` is then a function providing the register at the current base
+/// address.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// static PERIPHERAL_BASE: PeripheralBase = PeripheralBase::new(0x3F00_0000);
+///
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@dyn(PERIPHERAL_BASE + 0x20_0000)> {
+///         BAR OFFSET(0)
+///     }
+/// );
+///
+/// fn main() {
+///     // the peripherals are mapped to a different address, e.g. on a Raspberry Pi 4
+///     PERIPHERAL_BASE.init_once(0xFE00_0000).expect("peripheral base set twice");
+///     FOO::Register().modify(FOO::BAR, 1);
+/// }
+/// ```
+///
 /// Define multiple MMIO register at once
 /// ```no_run
 /// # use ruspiro_mmio_register::*;
 /// define_mmio_register!(
-///     FOO<ReadWrite<u32>@(0x3F20_0000)>,
-///     BAR<ReadOnly<u32>@(0x3F20_0010)> {
-///         BAZ OFFSET(0) BITS(2) [
-///             VAL1 = 0b10
+///     FOO<ReadWrite<u32>@(0x3F20_0000)>,
+///     BAR<ReadOnly<u32>@(0x3F20_0010)> {
+///         BAZ OFFSET(0) BITS(2) [
+///             VAL1 = 0b10
+///         ]
+///     }
+/// );
+/// ```
+///
+/// For each field with specific values defined an enum ``Value`` is provided within the module of the field. It
+/// allows to match on the value of the field.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// # use core::convert::TryFrom;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         MODE OFFSET(0) BITS(2) [
+///             IDLE = 0b00,
+///             RUN = 0b01,
+///             HALT = 0b11
+///         ]
+///     }
+/// );
+///
+/// fn main() {
+///     match FOO::MODE::Value::try_from(FOO::Register.read(FOO::MODE)) {
+///         Ok(FOO::MODE::Value::IDLE) => FOO::Register.modify_value(FOO::MODE::Value::RUN.into()),
+///         Ok(FOO::MODE::Value::RUN) | Ok(FOO::MODE::Value::HALT) => 0,
+///         Err(undefined) => panic!("undefined mode {:#b}", undefined),
+///     };
+/// }
+/// ```
+///
+/// The module of each field also provides the names and values of its specific values as ``VARIANTS``, while
+/// ``name_of`` provides the name of the specific value matching a raw field value.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         MODE OFFSET(0) BITS(2) [
+///             IDLE = 0b00,
+///             RUN = 0b01
+///         ]
+///     }
+/// );
+///
+/// fn main() {
+///     for (name, value) in FOO::MODE::VARIANTS {
+///         println!("{} = {:#b}", name, value);
+///     }
+///     let mode = FOO::MODE::name_of(FOO::Register.read(FOO::MODE)).unwrap_or("undefined");
+/// }
+/// ```
+///
+/// ``write_checked`` writes a raw field value only if it is one of the specific values defined for the field, e.g. to
+/// reject a value received from outside of the driver that the hardware does not define.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         MODE OFFSET(0) BITS(2) [
+///             IDLE = 0b00,
+///             RUN = 0b01
+///         ]
+///     }
+/// );
+///
+/// fn main() {
+///     assert_eq!(FOO::Register.write_checked(FOO::MODE, 0b11), Err(InvalidEncoding { value: 0b11 }));
+/// }
+/// ```
+///
+/// The module of each field provides the ``MASK``, ``SHIFT`` and ``WIDTH`` of the field as constants, e.g. to
+/// precompute a packed register value in a ``static`` without accessing the register.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         DIV OFFSET(4) BITS(3)
+///     }
+/// );
+///
+/// static DIV_BY_4: u32 = (4 << FOO::DIV::SHIFT) & FOO::DIV::MASK;
+///
+/// fn main() {
+///     assert_eq!(FOO::DIV::WIDTH, 3);
+///     FOO::Register.set(DIV_BY_4);
+/// }
+/// ```
+///
+/// The fields of a register must not overlap. A field covering bits of another field of the same register fails to
+/// compile.
+/// ```compile_fail
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         BAR OFFSET(0) BITS(4),
+///         BAZ OFFSET(3) BITS(2)
+///     }
+/// );
+/// # fn main() {}
+/// ```
+///
+/// Each field name may only be used once within a register. A repeated field name fails to compile naming the field
+/// and the register.
+/// ```compile_fail
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         BAR OFFSET(0) BITS(4),
+///         BAR OFFSET(4) BITS(4)
+///     }
+/// );
+/// # fn main() {}
+/// ```
+///
+/// The fields also need to fit into the width of the register.
+/// ```compile_fail
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         BAR OFFSET(30) BITS(4)
+///     }
+/// );
+/// # fn main() {}
+/// ```
+///
+/// The address of the register needs to be aligned to the width of the register, as accessing a misaligned register
+/// faults.
+/// ```compile_fail
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0002)>
+/// );
+/// # fn main() {}
+/// ```
+///
+/// A field needs to cover at least one bit, a field with ``BITS(0)`` would never access the register.
+/// ```compile_fail
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         BAR OFFSET(4) BITS(0)
+///     }
+/// );
+/// # fn main() {}
+/// ```
+///
+/// The range of bits a field covers must not be descending.
+/// ```compile_fail
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         BAR BITS(7..=4)
+///     }
+/// );
+/// # fn main() {}
+/// ```
+///
+/// An alias needs to refer to a field of the same register.
+/// ```compile_fail
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         BAR OFFSET(0),
+///         BAZ ALIAS_OF(BAL)
+///     }
+/// );
+/// # fn main() {}
+/// ```
+///
+/// Define a MMIO register where one field has defined specific values to be choosen from when
+/// writing to or updating this specific register field
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     /// A MMIO Register FOO
+///     /// Defines as Read/Write register
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         /// This is a register field BAR
+///         BAR OFFSET(3) BITS(3),
+///         /// This is a register field BAZ.
+///         /// It contains enum like field value definitions
+///         BAZ OFFSET(6) BITS(3) [
+///             /// This is a value of the register field
+///             VAL1 = 0b000,
+///             VAL2 = 0b010
+///         ],
+///         BAL OFFSET(9) BITS(2) [
+///             VAL1 = 0b01,
+///             VAL2 = 0b11
+///         ]
+///     }
+/// );
+///
+/// fn main() {
+///     // write a specific value for one field of the MMIO register
+///     FOO::Register.write_value(
+///         FOO::BAL::VAL1
+///     );
+///
+///     // combine field values of different fields to update the MMIO register
+///     FOO::Register.write_value(
+///         FOO::BAZ::VAL1 | FOO::BAL::VAL2
+///     );
+///
+///     // write a specific value to a register field that does not provide default/enum values
+///     FOO::Register.write_value(
+///         FOO::BAR::with_value(0b010)
+///     );
+/// }
+/// ```
+#[macro_export]
+macro_rules! define_mmio_register {
+    // REGISTER_NAME<ReadWrite<TYPE>@ADDRESS = RESET> { FIELD OFFSET(num) BITS(num) [ VALUE: val ] }
+    ($($(#[doc = $rdoc:expr])* $vis:vis $name:ident<
+        $access:ident<$t:ty>@$($kind:ident)?($($addr:tt)+) $(= $reset:literal)?
+    > $(
+        { $($fields:tt)* }
+    )?),*) => {
+        $(
+            $crate::define_mmio_register!(
+                @parse define_mmio_register
+                { $(#[doc = $rdoc])* $vis $name, $access, $t, [$($kind)?] ($($addr)+), [$($reset)?] }
+                $($($fields)*)?
+            );
+        )*
+    };
+
+    // internal rule: the register module containing the register and its fields
+    (@register {
+        $(#[doc = $rdoc:expr])* $vis:vis $name:ident, $access:ident, $t:ty, [$($kind:ident)?] ($($addr:tt)+),
+        [$($reset:literal)?] $(, { [$($aattr:tt)*] [$($avis:tt)*] $alias:ident $other:ident })*
+    } { [$($w1c:tt)*] [$($rc:tt)*] [$($reserved:tt)*] } $($field:tt)*) => {
+        #[allow(non_snake_case)]
+        #[allow(non_upper_case_globals)]
+        // the generated items use the fields of the register, only the uses of deprecated fields outside of the
+        // register module shall warn
+        #[allow(deprecated)]
+        $vis mod $name {
+            #[allow(unused_imports)]
+            use $crate::*;
+            use super::*;
+            $crate::define_mmio_register!(@handle $name, $t, [$($rc)*], $($field)*);
+            $crate::define_mmio_register!(@methods $t, [$($w1c)*] [$($rc)*] [$($reserved)*], $($field)*);
+            $crate::define_mmio_register!(@reset $t, $($reset)?);
+            $crate::define_mmio_register!(
+                @register_const $name {
+                    $(#[doc = $rdoc])*
+                    #[doc = $crate::define_mmio_register!(@rc_doc [$($rc)*])]
+                } $access, $t, [$($kind)?] ($($addr)+)
+            );
+            $crate::define_mmio_register!(@fields $t, $($field)*);
+            $crate::define_mmio_register!(@aliases $({ [$($aattr)*] [$($avis)*] $alias $other })*);
+            $crate::define_mmio_register!(@check $name, $t, $($field)*);
+        }
+    };
+
+    // internal rule: the register accessing the address stored within the register access type
+    (@register_const $name:ident { $($attr:tt)* } $access:ident, $t:ty, [] ($addr:expr)) => {
+        $($attr)*
+        #[allow(unused_variables, dead_code)]
+        pub const Register: Handle<$access<$t>> = Handle($access::<$t>::new($addr));
+        $crate::define_mmio_register!(@aligned $name, $access, $t, "address" $addr);
+        $crate::define_mmio_register!(@addr $addr);
+    };
+
+    // internal rule: the zero sized register with the address as part of its type
+    (@register_const $name:ident { $($attr:tt)* } $access:ident, $t:ty, [const] ($addr:expr)) => {
+        $($attr)*
+        #[allow(unused_variables, dead_code)]
+        pub const Register: Handle<Reg<$access<$t>, { $addr }>> = Handle(Reg::new());
+        $crate::define_mmio_register!(@aligned $name, $access, $t, "address" $addr);
+        $crate::define_mmio_register!(@addr $addr);
+    };
+
+    // internal rule: the register relative to a peripheral base address that is only known at runtime
+    (@register_const $name:ident { $($attr:tt)* } $access:ident, $t:ty, [dyn] ($base:ident + $offset:expr)) => {
+        $($attr)*
+        #[inline]
+        #[allow(dead_code)]
+        pub fn Register() -> Handle<$access<$t>> {
+            Handle($access::<$t>::new($base.base() + $offset))
+        }
+    };
+
+    // internal rule: the address of the register usable in const context
+    (@addr $addr:expr) => {
+        /// Provide the address of the register. This does not access the register and could be used in const
+        /// context, e.g. to build a static table of register addresses.
+        #[inline]
+        #[allow(dead_code)]
+        pub const fn addr() -> usize {
+            $addr
+        }
+    };
+
+    // internal rule: compile time check that the register address is aligned to the register width, as accessing a
+    // misaligned register faults
+    (@aligned $name:ident, $access:ident, $t:ty, $what:literal $addr:expr) => {
+        const _: () = assert!(
+            ($addr) % $crate::define_mmio_register!(@align_of $access, $t) == 0,
+            concat!(
+                "the ", $what, " ", stringify!($addr), " of register ", stringify!($name),
+                " is not aligned to its width"
+            )
+        );
+    };
+
+    // internal rule: the alignment the register address requires, the split register is accessed in 32 bit halves
+    (@align_of Split64, $t:ty) => {
+        core::mem::align_of::<u32>()
+    };
+    (@align_of $access:ident, $t:ty) => {
+        core::mem::align_of::<$t>()
+    };
+
+    // internal rule: normalize the field definitions one by one into
+    // { [ATTRIBUTES] [CFG] [VISIBILITY] FIELD OFFSET BITS [MODIFIERS] [VALUES] } and pass them to the @register rule
+    // of the calling macro. CFG are the predicates of the ``cfg`` attributes of the field, each followed by a comma.
+    // Fields without visibility given are public. The fields marked W1C, RC and RESERVED are collected on the way as
+    // { FIELD [CFG] OFFSET BITS } into the lists { [W1C] [RC] [RESERVED] } passed along.
+    // Each rule processes an attribute, the visibility, the field itself or one of its modifiers, so a field without
+    // attributes and modifiers takes a single step. This keeps the nesting of the expansion below the recursion limit
+    // of the compiler even for registers defining a field for each of their bits.
+    (@parse $mac:ident { $($header:tt)* } $($fields:tt)*) => {
+        $crate::define_mmio_register!(@field $mac { $($header)* } [] { [] [] [] } { [] [] [] [pub] } $($fields)*);
+    };
+    (@field $mac:ident { $($header:tt)* } [$($done:tt)*] { $($collected:tt)* } { [] [] [] [pub] }) => {
+        $crate::$mac!(@register { $($header)* } { $($collected)* } $($done)*);
+    };
+    // the predicates of the ``cfg`` attributes are picked out of the attributes of the field. The code generated for
+    // the register referring to the field is only compiled if all of them hold.
+    (@field $mac:ident { $($header:tt)* } [$($done:tt)*] { $($collected:tt)* }
+        { [$($cfg:tt)*] [$($kept:tt)*] [$($fmt:ident)*] [$($vis:tt)*] } #[cfg($($predicate:tt)*)] $($rest:tt)*
+    ) => {
+        $crate::define_mmio_register!(
+            @field $mac { $($header)* } [$($done)*] { $($collected)* }
+            { [$($cfg)* $($predicate)*,] [$($kept)* #[cfg($($predicate)*)]] [$($fmt)*] [$($vis)*] } $($rest)*
+        );
+    };
+    // the ``fmt`` attribute giving the notation the field is printed in is no real attribute, it is removed and
+    // turned into the modifier HEX or BIN
+    (@field $mac:ident { $($header:tt)* } [$($done:tt)*] { $($collected:tt)* }
+        { [$($cfg:tt)*] [$($kept:tt)*] [$($fmt:ident)*] [$($vis:tt)*] } #[fmt(hex)] $($rest:tt)*
+    ) => {
+        $crate::define_mmio_register!(
+            @field $mac { $($header)* } [$($done)*] { $($collected)* }
+            { [$($cfg)*] [$($kept)*] [$($fmt)* HEX] [$($vis)*] } $($rest)*
+        );
+    };
+    (@field $mac:ident { $($header:tt)* } [$($done:tt)*] { $($collected:tt)* }
+        { [$($cfg:tt)*] [$($kept:tt)*] [$($fmt:ident)*] [$($vis:tt)*] } #[fmt(bin)] $($rest:tt)*
+    ) => {
+        $crate::define_mmio_register!(
+            @field $mac { $($header)* } [$($done)*] { $($collected)* }
+            { [$($cfg)*] [$($kept)*] [$($fmt)* BIN] [$($vis)*] } $($rest)*
+        );
+    };
+    (@field $mac:ident { $($header:tt)* } [$($done:tt)*] { $($collected:tt)* }
+        { [$($cfg:tt)*] [$($kept:tt)*] [$($fmt:ident)*] [$($vis:tt)*] } #[fmt($($other:tt)*)] $($rest:tt)*
+    ) => {
+        compile_error!(concat!("unknown field notation fmt(", stringify!($($other)*), "), expected hex or bin"));
+    };
+    (@field $mac:ident { $($header:tt)* } [$($done:tt)*] { $($collected:tt)* }
+        { [$($cfg:tt)*] [$($kept:tt)*] [$($fmt:ident)*] [$($vis:tt)*] } #[$($attr:tt)*] $($rest:tt)*
+    ) => {
+        $crate::define_mmio_register!(
+            @field $mac { $($header)* } [$($done)*] { $($collected)* }
+            { [$($cfg)*] [$($kept)* #[$($attr)*]] [$($fmt)*] [$($vis)*] } $($rest)*
+        );
+    };
+    (@field $mac:ident { $($header:tt)* } [$($done:tt)*] { $($collected:tt)* }
+        { [$($cfg:tt)*] [$($kept:tt)*] [$($fmt:ident)*] [$($vis:tt)*] }
+        pub $(($($restriction:tt)+))? $field:ident $($rest:tt)*
+    ) => {
+        $crate::define_mmio_register!(
+            @field $mac { $($header)* } [$($done)*] { $($collected)* }
+            { [$($cfg)*] [$($kept)*] [$($fmt)*] [pub $(($($restriction)+))?] } $field $($rest)*
+        );
+    };
+    (@field $mac:ident { $($header:tt)* } [$($done:tt)*] { $($collected:tt)* }
+        { [$($cfg:tt)*] [$($kept:tt)*] [$($fmt:ident)*] [$($vis:tt)*] }
+        $field:ident ALIAS_OF($other:ident) $(, $($rest:tt)*)?
+    ) => {
+        $crate::define_mmio_register!(
+            @field $mac { $($header)*, { [$($kept)*] [$($vis)*] $field $other } } [$($done)*] { $($collected)* }
+            { [] [] [] [pub] } $($($rest)*)?
+        );
+    };
+    // the size is kept as token to allow the rules checking ``EXHAUSTIVE`` fields to match the number of bits. The
+    // fields without modifiers are completed right away, the others continue with their modifiers.
+    (@field $mac:ident { $($header:tt)* } [$($done:tt)*] { $($collected:tt)* }
+        { [$($cfg:tt)*] [$($kept:tt)*] [$($fmt:ident)*] [$($vis:tt)*] }
+        $field:ident OFFSET($offset:literal) BITS($bits:tt) $([$($values:tt)*])? $(, $($rest:tt)*)?
+    ) => {
+        $crate::define_mmio_register!(
+            @field $mac { $($header)* } [
+                $($done)* { [$($kept)*] [$($cfg)*] [$($vis)*] $field $offset $bits [$($fmt)*] [$($($values)*)?] }
+            ] { $($collected)* } { [] [] [] [pub] } $($($rest)*)?
+        );
+    };
+    (@field $mac:ident { $($header:tt)* } [$($done:tt)*] { $($collected:tt)* }
+        { [$($cfg:tt)*] [$($kept:tt)*] [$($fmt:ident)*] [$($vis:tt)*] }
+        $field:ident OFFSET($offset:literal) BITS($bits:tt) $($rest:tt)*
+    ) => {
+        $crate::define_mmio_register!(
+            @modifiers $mac { $($header)* } [$($done)*] { $($collected)* }
+            { [$($kept)*] [$($cfg)*] [$($vis)*] $field $offset $bits } [] [$($fmt)*] $($rest)*
+        );
+    };
+    (@field $mac:ident { $($header:tt)* } [$($done:tt)*] { $($collected:tt)* }
+        { [$($cfg:tt)*] [$($kept:tt)*] [$($fmt:ident)*] [$($vis:tt)*] }
+        $field:ident OFFSET($offset:literal) $([$($values:tt)*])? $(, $($rest:tt)*)?
+    ) => {
+        $crate::define_mmio_register!(
+            @field $mac { $($header)* } [
+                $($done)* { [$($kept)*] [$($cfg)*] [$($vis)*] $field $offset 1 [$($fmt)*] [$($($values)*)?] }
+            ] { $($collected)* } { [] [] [] [pub] } $($($rest)*)?
+        );
+    };
+    (@field $mac:ident { $($header:tt)* } [$($done:tt)*] { $($collected:tt)* }
+        { [$($cfg:tt)*] [$($kept:tt)*] [$($fmt:ident)*] [$($vis:tt)*] }
+        $field:ident OFFSET($offset:literal) $($rest:tt)*
+    ) => {
+        $crate::define_mmio_register!(
+            @modifiers $mac { $($header)* } [$($done)*] { $($collected)* }
+            { [$($kept)*] [$($cfg)*] [$($vis)*] $field $offset 1 } [] [$($fmt)*] $($rest)*
+        );
+    };
+    (@field $mac:ident { $($header:tt)* } [$($done:tt)*] { $($collected:tt)* }
+        { [$($cfg:tt)*] [$($kept:tt)*] [$($fmt:ident)*] [$($vis:tt)*] }
+        $field:ident BITS($low:literal..=$high:literal) $($rest:tt)*
+    ) => {
+        $crate::define_mmio_register!(
+            @modifiers $mac { $($header)* } [$($done)*] { $($collected)* }
+            { [$($kept)*] [$($cfg)*] [$($vis)*] $field $low ({
+                assert!($low <= $high, concat!("the bit range of field ", stringify!($field), " is descending"));
+                $high + 1 - $low
+            }) } [] [$($fmt)*] $($rest)*
+        );
+    };
+
+    // internal rule: the modifiers following the field offset and size. The fields marked W1C, RC or RESERVED are
+    // added to the list of the fields collected with this modifier.
+    (@modifiers $mac:ident { $($header:tt)* } [$($done:tt)*] { [$($w1c:tt)*] [$($rc:tt)*] [$($reserved:tt)*] }
+        { [$($kept:tt)*] [$($cfg:tt)*] [$($vis:tt)*] $field:ident $offset:tt $bits:tt } [$($modifier:ident)*]
+        [$($fmt:ident)*] W1C $($rest:tt)*
+    ) => {
+        $crate::define_mmio_register!(
+            @modifiers $mac { $($header)* } [$($done)*]
+            { [$($w1c)* { $field [$($cfg)*] $offset $bits }] [$($rc)*] [$($reserved)*] }
+            { [$($kept)*] [$($cfg)*] [$($vis)*] $field $offset $bits } [$($modifier)* W1C] [$($fmt)*] $($rest)*
+        );
+    };
+    (@modifiers $mac:ident { $($header:tt)* } [$($done:tt)*] { [$($w1c:tt)*] [$($rc:tt)*] [$($reserved:tt)*] }
+        { [$($kept:tt)*] [$($cfg:tt)*] [$($vis:tt)*] $field:ident $offset:tt $bits:tt } [$($modifier:ident)*]
+        [$($fmt:ident)*] RC $($rest:tt)*
+    ) => {
+        $crate::define_mmio_register!(
+            @modifiers $mac { $($header)* } [$($done)*]
+            { [$($w1c)*] [$($rc)* { $field [$($cfg)*] $offset $bits }] [$($reserved)*] }
+            { [$($kept)*] [$($cfg)*] [$($vis)*] $field $offset $bits } [$($modifier)* RC] [$($fmt)*] $($rest)*
+        );
+    };
+    (@modifiers $mac:ident { $($header:tt)* } [$($done:tt)*] { [$($w1c:tt)*] [$($rc:tt)*] [$($reserved:tt)*] }
+        { [$($kept:tt)*] [$($cfg:tt)*] [$($vis:tt)*] $field:ident $offset:tt $bits:tt } [$($modifier:ident)*]
+        [$($fmt:ident)*] RESERVED $($rest:tt)*
+    ) => {
+        $crate::define_mmio_register!(
+            @modifiers $mac { $($header)* } [$($done)*]
+            { [$($w1c)*] [$($rc)*] [$($reserved)* { $field [$($cfg)*] $offset $bits }] }
+            { [$($kept)*] [$($cfg)*] [$($vis)*] $field $offset $bits } [$($modifier)* RESERVED] [$($fmt)*] $($rest)*
+        );
+    };
+    (@modifiers $mac:ident { $($header:tt)* } [$($done:tt)*] { $($collected:tt)* } { $($field:tt)* }
+        [$($modifier:ident)*] [$($fmt:ident)*] EXHAUSTIVE $($rest:tt)*
+    ) => {
+        $crate::define_mmio_register!(
+            @modifiers $mac { $($header)* } [$($done)*] { $($collected)* } { $($field)* }
+            [$($modifier)* EXHAUSTIVE] [$($fmt)*] $($rest)*
+        );
+    };
+    (@modifiers $mac:ident { $($header:tt)* } [$($done:tt)*] { $($collected:tt)* } { $($field:tt)* }
+        [$($modifier:ident)*] [$($fmt:ident)*] TYPED $($rest:tt)*
+    ) => {
+        $crate::define_mmio_register!(
+            @modifiers $mac { $($header)* } [$($done)*] { $($collected)* } { $($field)* }
+            [$($modifier)* TYPED] [$($fmt)*] $($rest)*
+        );
+    };
+    (@modifiers $mac:ident { $($header:tt)* } [$($done:tt)*] { $($collected:tt)* } { $($field:tt)* }
+        [$($modifier:ident)*] [$($fmt:ident)*] $([$($values:tt)*])? $(, $($rest:tt)*)?
+    ) => {
+        $crate::define_mmio_register!(
+            @field $mac { $($header)* } [
+                $($done)* { $($field)* [$($modifier)* $($fmt)*] [$($($values)*)?] }
+            ] { $($collected)* } { [] [] [] [pub] } $($($rest)*)?
+        );
+    };
+
+    // internal rule: the handle of a register providing the decoded contents as debug output
+    (@handle $name:ident, $t:ty, [$($rc:tt)*], $($field:tt)*) => {
+        $crate::define_mmio_register!(@handle_type);
+        $crate::define_mmio_register!(@snapshot $name, $t, $($field)*);
+        $crate::define_mmio_register!(@valid_encodings $t, $($field)*);
+
+        impl<A: Readable<Width = $t>> Handle<A> {
+            /// Read the contents of the register into a ``Values`` snapshot with a single read of the register. The
+            /// fields are decoded from the snapshot, so they provide a consistent view of the register contents.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn read_all(&self) -> Values {
+                Values::from_raw(self.0.get())
+            }
+
+            /// Check whether the register contains the value of one or more of its fields. Only the field values
+            /// of this register are accepted.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn matches_value(&self, fieldvalue: TypedFieldValue<Values, $t>) -> bool {
+                self.0.get() & fieldvalue.mask() == fieldvalue.raw_value() & fieldvalue.mask()
+            }
+
+            /// Read the current contents of several fields of the register with a single read into one field value
+            /// bound to this register. This allows to save some fields and restore them later with ``modify_value``
+            /// without touching the other bits.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn read_values(&self, fields: &[RegisterField<$t>]) -> TypedFieldValue<Values, $t> {
+                let mask = fields.iter().fold(0, |mask, field| mask | field.mask());
+                // the mask covers all fields to read, so it is used as field without a shift
+                let field = RegisterField::<$t>::new(mask, 0);
+                TypedFieldValue::<Values, $t>::new(RegisterFieldValue::<$t>::new(field, self.0.get()))
+            }
+
+            /// Read the value of a field marked with ``TYPED`` as the value type of this field, that is given with
+            /// the type of the result, e.g. ``let bar: FOO::BarValue = FOO::Register.read_typed();``. Only the
+            /// fields of this register are accepted.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn read_typed<F: TypedField<Width = $t, Register = Values>>(&self) -> F {
+                F::from_raw((self.0.get() & F::FIELD.mask()) >> F::FIELD.shift())
+            }
+        }
+
+        impl<A: Writeable<Width = $t>> Handle<A> {
+            /// Write the value of one or more of the register fields, this will set all bits not coverd by those
+            /// fields to 0 ! Only the field values of this register are accepted.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn write_value(&self, fieldvalue: TypedFieldValue<Values, $t>) {
+                self.0.set(fieldvalue.raw_value());
+            }
+
+            /// Write the values of several register fields with a single write of the register, e.g. to configure a
+            /// write only register. All bits not coverd by any of the field values are set to 0 ! Only the field
+            /// values of this register are accepted.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn write_values(&self, fieldvalues: &[TypedFieldValue<Values, $t>]) {
+                self.0.set(pack(fieldvalues));
+            }
+
+            /// Write the value of a specific register field, this will set all bits not coverd by this field to 0 !
+            /// If specific values are defined for the field, the value needs to be one of them. Otherwise the
+            /// register is not written and the error containing the value is returned. Fields without specific
+            /// values accept any value.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn write_checked(&self, field: RegisterField<$t>, value: $t) -> Result<(), InvalidEncoding<$t>> {
+                let encodings = valid_encodings(field);
+                if !encodings.is_empty() && !encodings.iter().any(|(_, encoding)| *encoding == value) {
+                    return Err(InvalidEncoding { value });
+                }
+                self.0.set((value << field.shift()) & field.mask());
+                Ok(())
+            }
+        }
+
+        impl<A: Modifiable<Width = $t>> Handle<A> {
+            /// Udate one or more of the register fields with the field value given. The bits outside of those fields
+            /// remains untouched. Only the field values of this register are accepted.
+            /// The function returns the register raw value set has been set with this update
+            #[inline]
+            #[allow(dead_code)]
+            pub fn modify_value(&self, fieldvalue: TypedFieldValue<Values, $t>) -> $t {
+                // the mask of the field value covers all fields to update, so it is used as field without a shift
+                self.0.modify(RegisterField::<$t>::new(fieldvalue.mask(), 0), fieldvalue.raw_value())
+            }
+
+            /// Udate the fields of several field values with a single read-modify-write of the register. The bits
+            /// outside of those fields remains untouched. If fields of the given values overlap, they are applied in
+            /// the order given, so the last one wins. Only the field values of this register are accepted.
+            /// The function returns the register raw value set has been set with this update
+            #[inline]
+            #[allow(dead_code)]
+            pub fn modify_fields(&self, fieldvalues: &[TypedFieldValue<Values, $t>]) -> $t {
+                let update = |old_val: $t| {
+                    fieldvalues.iter().fold(old_val, |val, fieldvalue| {
+                        (val & !fieldvalue.mask()) | (fieldvalue.raw_value() & fieldvalue.mask())
+                    })
+                };
+                self.0.rmw(update).1
+            }
+
+            /// Udate one or more of the register fields with the field value given. The bits outside of those fields
+            /// remains untouched. Only the field values of this register are accepted. The function returns the
+            /// field value the fields contained before this update, to restore them later with ``modify_value``.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn replace_value(&self, fieldvalue: TypedFieldValue<Values, $t>) -> TypedFieldValue<Values, $t> {
+                TypedFieldValue::<Values, $t>::new(self.0.replace_value(fieldvalue.value()))
+            }
+
+            /// Udate a field marked with ``TYPED`` with the value of its value type. The bits outside of this field
+            /// remains untouched. Only the fields of this register are accepted.
+            /// The function returns the register raw value set has been set with this update
+            #[inline]
+            #[allow(dead_code)]
+            pub fn modify_typed<F: TypedField<Width = $t, Register = Values>>(&self, value: F) -> $t {
+                self.0.modify(F::FIELD, value.raw())
+            }
+
+            /// Write the value of one or more of the register fields while preserving all bits not covered by those
+            /// fields. In contrast to ``write_value`` the register is read first and only the bits of the fields
+            /// given are updated. Only the field values of this register are accepted.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn update_value(&self, fieldvalue: TypedFieldValue<Values, $t>) {
+                self.modify_value(fieldvalue);
+            }
+        }
+
+        impl<A: Modifiable<Width = $t>> Handle<A> {
+            /// Update the register with a single read-modify-write of the whole register. The closure receives the
+            /// ``Values`` snapshot read from the register and returns the snapshot to be written to it. The update
+            /// is performed with the ``rmw`` of the register access type, so it is atomic for ``AtomicReadWrite``
+            /// registers, where the closure is called again if the register changed in between.
+            /// The function returns the register raw value set has been set with this update
+            #[inline]
+            #[allow(dead_code)]
+            pub fn modify_all<F: Fn(Values) -> Values>(&self, f: F) -> $t {
+                self.0.rmw(|old_val| f(Values::from_raw(old_val)).raw()).1
+            }
+        }
+
+        $crate::define_mmio_register!(@handle_debug $name, $t, [$($rc)*]);
+    };
+
+    // internal rule: the debug output of the register handle, reading the register unless this clears some of its
+    // fields
+    (@handle_debug $name:ident, $t:ty, []) => {
+        /// The debug output of the register reads the register and provides its decoded contents.
+        impl<A: Readable<Width = $t>> core::fmt::Debug for Handle<A> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Debug::fmt(&self.read_all(), f)
+            }
+        }
+    };
+    (@handle_debug $name:ident, $t:ty, [$({ $rc:ident [$($cfg:tt)*] $offset:tt $bits:tt })+]) => {
+        /// The debug output of the register reads the register and provides its decoded contents. As reading a
+        /// register with read-to-clear fields clears them, such a register is not read and printed as
+        /// ``<read clears>`` instead. Use ``read_all`` to print its contents explicitly.
+        impl<A: Readable<Width = $t>> core::fmt::Debug for Handle<A> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                if false $(|| cfg!(all($($cfg)*)))+ {
+                    f.debug_tuple(stringify!($name)).field(&format_args!("<read clears>")).finish()
+                } else {
+                    core::fmt::Debug::fmt(&self.read_all(), f)
+                }
+            }
+        }
+    };
+
+    // internal rule: the snapshot of the contents of a register without fields
+    (@snapshot $name:ident, $t:ty,) => {
+        $crate::define_mmio_register!(@snapshot_type $t, !0);
+
+        impl core::fmt::Debug for Values {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_tuple(stringify!($name))
+                    .field(&$crate::debug::FieldDebug::new(self.raw, &[], $crate::debug::Radix::Binary))
+                    .finish()
+            }
+        }
+
+        impl core::fmt::Display for Values {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                $crate::debug::display_values(f, stringify!($name), self.raw, &[])
+            }
+        }
+
+        impl core::fmt::Debug for Diff {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut debug = f.debug_tuple(stringify!($name));
+                if self.old.raw != self.new.raw {
+                    debug.field(&$crate::debug::FieldDiff::new(
+                        self.old.raw,
+                        self.new.raw,
+                        &[],
+                        $crate::debug::Radix::Binary,
+                    ));
+                }
+                debug.finish()
+            }
+        }
+
+        $crate::__defmt_impl! {
+            impl $crate::defmt::Format for Values {
+                fn format(&self, f: $crate::defmt::Formatter<'_>) {
+                    $crate::format::format_values(f, stringify!($name), self.raw, &[]);
+                }
+            }
+        }
+    };
+
+    // internal rule: the snapshot of the contents of a register decoding each field
+    (@snapshot $name:ident, $t:ty, $(
+        { [$($attr:tt)*] [$($cfg:tt)*] [$($vis:tt)*] $field:ident $offset:tt $bits:tt [$($modifier:ident)*]
+            [$($values:tt)*] }
+    )+) => {
+        $crate::define_mmio_register!(
+            @snapshot_type $t, 0 $(| $crate::define_mmio_register!(
+                @gated [$($cfg)*] $crate::define_mmio_register!(@compared $t, $offset, $bits, [$($modifier)*])
+            ))+
+        );
+
+        $crate::paste::paste! {
+            impl Values {
+                $(
+                    $($attr)*
+                    #[inline]
+                    #[allow(dead_code)]
+                    $($vis)* fn [<$field:lower>](&self) -> $t {
+                        (self.raw & $field.mask()) >> $field.shift()
+                    }
+
+                    $($attr)*
+                    #[inline]
+                    #[allow(dead_code)]
+                    #[must_use]
+                    $($vis)* fn [<with_ $field:lower>](self, value: $t) -> Self {
+                        Self::from_raw((self.raw & !$field.mask()) | ((value << $field.shift()) & $field.mask()))
+                    }
+                )+
+            }
+        }
+
+        impl<A> Builder<'_, A> {
+            $(
+                $crate::define_mmio_register!(@setter $t, [$($attr)*] [$($vis)*] $field [$($values)*]);
+            )+
+        }
+
+        /// Builder composing the contents of the register field by field to update the register with a single
+        /// access. It starts from the reset value of the register if known, otherwise with all bits cleared. Each
+        /// field is set with the function named like the field in lower case, that accepts the specific ``Value`` of
+        /// the field if there are specific values defined and the raw field value otherwise.
+        #[must_use]
+        pub struct Builder<'a, A> {
+            register: &'a Handle<A>,
+            values: Values,
+            mask: $t,
+        }
+
+        impl<A: Writeable<Width = $t>> Handle<A> {
+            /// Provide the builder to compose the contents of the register field by field
+            #[inline]
+            #[allow(dead_code)]
+            pub fn build(&self) -> Builder<'_, A> {
+                Builder {
+                    register: self,
+                    values: Values::default(),
+                    mask: 0,
+                }
+            }
+        }
+
+        impl<A> Builder<'_, A> {
+            /// Set the value of one or more fields given as register field value
+            #[inline]
+            #[allow(dead_code)]
+            pub fn value(mut self, fieldvalue: TypedFieldValue<Values, $t>) -> Self {
+                let raw_val = self.values.raw() & !fieldvalue.mask();
+                self.values = Values::from_raw(raw_val | (fieldvalue.raw_value() & fieldvalue.mask()));
+                self.mask |= fieldvalue.mask();
+                self
+            }
+        }
+
+        impl<A: Writeable<Width = $t>> Builder<'_, A> {
+            /// Write the composed contents to the register with a single write. The fields not set with this builder
+            /// are written with their reset value, or 0 if there is no reset value known.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn write(self) {
+                self.values.write(self.register);
+            }
+        }
+
+        impl<A: Modifiable<Width = $t>> Builder<'_, A> {
+            /// Update the fields set with this builder with a single read-modify-write of the register. The fields not
+            /// set with this builder remains untouched. This is performed with the ``rmw`` of the register access
+            /// type, so it is atomic for ``AtomicReadWrite`` registers.
+            /// The function returns the register raw value set has been set with this update
+            #[inline]
+            #[allow(dead_code)]
+            pub fn modify(self) -> $t {
+                let raw_val = self.values.raw() & self.mask;
+                self.register.0.rmw(|old_val| (old_val & !self.mask) | raw_val).1
+            }
+        }
+
+        impl core::fmt::Debug for Values {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut debug = f.debug_struct(stringify!($name));
+                $(
+                    #[cfg(all($($cfg)*))]
+                    {
+                        let value = (self.raw & $field.mask()) >> $field.shift();
+                        let radix = $crate::define_mmio_register!(@radix $bits [$($modifier)*]);
+                        let value = $crate::debug::FieldDebug::new(value, $field::VARIANTS, radix);
+                        debug.field(stringify!($field), &value);
+                    }
+                )*
+                debug.finish()
+            }
+        }
+
+        impl core::fmt::Display for Values {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                $crate::debug::display_values(f, stringify!($name), self.raw, &[
+                    $(
+                        // the field is not referred to by name as it might be compiled out
+                        if cfg!(all($($cfg)*)) {
+                            Some($crate::debug::FieldDisplay::new(
+                                stringify!($field),
+                                (self.raw & $crate::define_mmio_register!(@mask $t, $offset, $bits)) >> $offset,
+                                $crate::define_mmio_register!(@variants $($values)*),
+                                $crate::define_mmio_register!(@radix $bits [$($modifier)*]),
+                            ))
+                        } else {
+                            None
+                        }
+                    ),*
+                ])
+            }
+        }
+
+        impl core::fmt::Debug for Diff {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut debug = f.debug_struct(stringify!($name));
+                $(
+                    #[cfg(all($($cfg)*))]
+                    {
+                        let old_val = (self.old.raw & $field.mask()) >> $field.shift();
+                        let new_val = (self.new.raw & $field.mask()) >> $field.shift();
+                        if old_val != new_val {
+                            let radix = $crate::define_mmio_register!(@radix $bits [$($modifier)*]);
+                            let diff = $crate::debug::FieldDiff::new(old_val, new_val, $field::VARIANTS, radix);
+                            debug.field(stringify!($field), &diff);
+                        }
+                    }
+                )*
+                debug.finish()
+            }
+        }
+
+        $crate::__defmt_impl! {
+            impl $crate::defmt::Format for Values {
+                fn format(&self, f: $crate::defmt::Formatter<'_>) {
+                    $crate::format::format_values(f, stringify!($name), self.raw, &[
+                        $(
+                            // the field is not referred to by name as it might be compiled out
+                            if cfg!(all($($cfg)*)) {
+                                Some($crate::format::FieldFormat::new(
+                                    stringify!($field),
+                                    (self.raw & $crate::define_mmio_register!(@mask $t, $offset, $bits)) >> $offset,
+                                    $crate::define_mmio_register!(@variants $($values)*),
+                                    $crate::define_mmio_register!(@radix $bits [$($modifier)*]),
+                                ))
+                            } else {
+                                None
+                            }
+                        ),*
+                    ]);
+                }
+            }
+        }
+    };
+
+    // internal rule: the builder function setting the raw value of a field without specific values
+    (@setter $t:ty, [$($attr:tt)*] [$($vis:tt)*] $field:ident []) => {
+        $crate::paste::paste! {
+            $($attr)*
+            #[inline]
+            #[allow(dead_code)]
+            $($vis)* fn [<$field:lower>](mut self, value: $t) -> Self {
+                self.values = self.values.[<with_ $field:lower>](value);
+                self.mask |= $field.mask();
+                self
+            }
+        }
+    };
+
+    // internal rule: the builder function setting one of the specific values of a field
+    (@setter $t:ty, [$($attr:tt)*] [$($vis:tt)*] $field:ident [$($values:tt)+]) => {
+        $crate::paste::paste! {
+            $($attr)*
+            #[inline]
+            #[allow(dead_code)]
+            $($vis)* fn [<$field:lower>](mut self, value: $field::Value) -> Self {
+                self.values = self.values.[<with_ $field:lower>](value.into());
+                self.mask |= $field.mask();
+                self
+            }
+        }
+    };
+
+    // internal rule: the snapshot type shared by all registers
+    (@snapshot_type $t:ty, $compared:expr) => {
+        /// Snapshot of the register contents read with a single read of the register. It provides the value of each
+        /// field decoded from this snapshot without accessing the register again.
+        ///
+        /// Two snapshots are equal if all fields of the register contain the same value. The bits not covered by
+        /// any field and the bits of reserved fields are not compared, so the comparison does not depend on bits the
+        /// hardware does not define.
+        #[derive(Clone, Copy)]
+        pub struct Values {
+            raw: $t,
+        }
+
+        impl PartialEq for Values {
+            fn eq(&self, other: &Self) -> bool {
+                self.raw & ($compared) == other.raw & ($compared)
+            }
+        }
+
+        impl Eq for Values {}
+
+        impl core::hash::Hash for Values {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                (self.raw & ($compared)).hash(state);
+            }
+        }
+
+        impl Values {
+            /// Create the snapshot from the raw register value
+            #[inline]
+            #[allow(dead_code)]
+            pub const fn from_raw(raw: $t) -> Self {
+                Self { raw }
+            }
+
+            /// Provide the raw register value of the snapshot
+            #[inline]
+            #[allow(dead_code)]
+            pub const fn raw(&self) -> $t {
+                self.raw
+            }
+
+            /// Write the snapshot to the register with a single write
+            #[inline]
+            #[allow(dead_code)]
+            pub fn write<A: Writeable<Width = $t>>(self, register: &Handle<A>) {
+                register.0.set(self.raw);
+            }
+
+            /// Provide the mask of the bits that differ between this and the ``other`` snapshot. Like the comparison
+            /// of the snapshots, only the bits of the fields are compared, so this is 0 if both snapshots are equal.
+            #[inline]
+            #[allow(dead_code)]
+            pub const fn changed_fields(&self, other: &Values) -> $t {
+                (self.raw ^ other.raw) & ($compared)
+            }
+
+            /// Provide the debug output of the fields whose decoded value differs between this snapshot and the
+            /// ``other`` one taken later. Each field is shown with its value in this snapshot followed by its value in
+            /// the ``other`` snapshot.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn diff_debug(&self, other: &Values) -> Diff {
+                Diff {
+                    old: *self,
+                    new: *other,
+                }
+            }
+        }
+
+        /// The fields that differ between two snapshots of the register, provided as debug output
+        #[derive(Clone, Copy)]
+        pub struct Diff {
+            old: Values,
+            new: Values,
+        }
+    };
+
+    // internal rule: the mask of a field compared with the snapshot of the register. Reserved fields are not compared.
+    (@compared $t:ty, $offset:tt, $bits:tt, []) => {
+        $crate::define_mmio_register!(@mask $t, $offset, $bits)
+    };
+    (@compared $t:ty, $offset:tt, $bits:tt, [RESERVED $($modifier:ident)*]) => {
+        0
+    };
+    (@compared $t:ty, $offset:tt, $bits:tt, [$other:ident $($modifier:ident)*]) => {
+        $crate::define_mmio_register!(@compared $t, $offset, $bits, [$($modifier)*])
+    };
+
+    // internal rule: the specific values defined for a field of the register. As the fields of a register do not
+    // overlap, the field is identified by its mask.
+    (@valid_encodings $t:ty, $(
+        { [$($attr:tt)*] [$($cfg:tt)*] [$($vis:tt)*] $field:ident $offset:tt $bits:tt [$($modifier:ident)*]
+            [$($values:tt)*] }
+    )*) => {
+        #[allow(dead_code, unused_variables)]
+        fn valid_encodings(field: RegisterField<$t>) -> &'static [(&'static str, $t)] {
+            $(
+                #[cfg(all($($cfg)*))]
+                if field.mask() == $field.mask() {
+                    return $field::VARIANTS;
+                }
+            )*
+            &[]
+        }
+    };
+
+    // internal rule: the handle type shared by all registers
+    (@handle_type) => {
+        /// Access handle of the register. It dereferences to the register access type and provides the decoded
+        /// current contents of the register as its debug output.
+        #[derive(Clone)]
+        pub struct Handle<A>(A);
+
+        impl<A> core::ops::Deref for Handle<A> {
+            type Target = A;
+
+            fn deref(&self) -> &A {
+                &self.0
+            }
+        }
+    };
+
+    // internal rule: the notation the raw value of a field is printed in, given with the ``fmt`` attribute of the
+    // field or depending on its size
+    (@radix $bits:tt [$($modifier:ident)*]) => {
+        $crate::debug::Radix::of_field($crate::define_mmio_register!(@radix_hint [$($modifier)*]), $bits)
+    };
+    (@radix_hint []) => { None };
+    (@radix_hint [HEX $($modifier:ident)*]) => { Some($crate::debug::Radix::Hex) };
+    (@radix_hint [BIN $($modifier:ident)*]) => { Some($crate::debug::Radix::Binary) };
+    (@radix_hint [$other:ident $($modifier:ident)*]) => { $crate::define_mmio_register!(@radix_hint [$($modifier)*]) };
+
+    // internal rule: the names and values of the specific values defined for a field
+    (@variants $($(#[doc = $fvdoc:expr])* $enum:ident = $value:expr),*) => {
+        &[$((stringify!($enum), $value)),*]
+    };
+
+    // internal rule: the methods of the register handle depending on the modifiers of its fields
+    (@methods $t:ty, [$($w1c:tt)*] [$($rc:tt)*] [$($reserved:tt)*], $($field:tt)*) => {
+        $crate::define_mmio_register!(@field_infos $($field)*);
+
+        /// Pack the field values of this register into the raw register value, e.g. to precompute the
+        /// configuration of the register as a ``const``. The bits not covered by any of the field values are 0.
+        #[allow(dead_code)]
+        pub const fn pack(values: &[TypedFieldValue<Values, $t>]) -> $t {
+            let mut raw: $t = 0;
+            let mut i = 0;
+            while i < values.len() {
+                raw |= values[i].raw_value() & values[i].mask();
+                i += 1;
+            }
+            raw
+        }
+
+        $crate::define_mmio_register!(@w1c $t, [$($w1c)*]);
+        $crate::define_mmio_register!(@rc $t, [$($rc)*]);
+        $crate::define_mmio_register!(@reserved $t, [$($reserved)*]);
+    };
+
+    // internal rule: the table of the layouts of the fields of the register
+    (@field_infos $(
+        { [$($attr:tt)*] [$($cfg:tt)*] [$($vis:tt)*] $field:ident $offset:tt $bits:tt [$($modifier:ident)*]
+            [$($values:tt)*] }
+    )*) => {
+        /// The name, offset and width of each field of this register in the order of their definition. Fields
+        /// compiled out with a ``cfg`` attribute are not contained.
+        #[allow(dead_code)]
+        pub const FIELDS: &[FieldInfo] = &$crate::compact_fields::<
+            { 0 $(+ $crate::define_mmio_register!(@gated [$($cfg)*] 1))* }
+        >(&[$(
+            // the field is not referred to by name as it might be compiled out
+            if cfg!(all($($cfg)*)) { Some(FieldInfo::new(stringify!($field), $offset, $bits)) } else { None }
+        ),*]);
+    };
+
+    // internal rule: the reset value of the register and the methods to restore it
+    (@reset $t:ty,) => {
+        impl Default for Values {
+            /// Create the snapshot with all bits cleared, as there is no reset value known for the register
+            fn default() -> Self {
+                Self::from_raw(0)
+            }
+        }
+    };
+    (@reset $t:ty, $reset:literal) => {
+        /// The value of the register after a reset of the device
+        #[allow(dead_code)]
+        pub const RESET: $t = $reset;
+
+        impl Values {
+            /// Create the snapshot containing the reset value of the register
+            #[inline]
+            #[allow(dead_code)]
+            pub const fn reset() -> Self {
+                Self::from_raw(RESET)
+            }
+        }
+
+        impl Default for Values {
+            /// Create the snapshot containing the reset value of the register
+            fn default() -> Self {
+                Self::reset()
+            }
+        }
+
+        impl<A: Writeable<Width = $t>> Handle<A> {
+            /// Write the reset value to the register
+            #[inline]
+            #[allow(dead_code)]
+            pub fn reset(&self) {
+                self.0.set(RESET);
+            }
+
+            /// Write the reset value to the register. This is the same as ``reset``.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn write_default(&self) {
+                self.reset();
+            }
+        }
+    };
+
+    // internal rule: the ``clear`` method of a register with write-1-to-clear fields
+    (@w1c $t:ty, []) => {};
+    (@w1c $t:ty, [$({ $w1c:ident [$($cfg:tt)*] $offset:tt $bits:tt })+]) => {
+        impl<A: Writeable<Width = $t>> Handle<A> {
+            /// Clear a write-1-to-clear field of the register. This writes 1 to the bits of this field and 0 to all
+            /// other bits, so other write-1-to-clear fields of the register remain untouched.<br>
+            /// In contrast to ``modify`` the register is not read before the write. A ``modify`` would write back
+            /// the 1 of every write-1-to-clear bit currently set and thereby clear them as well.
+            ///
+            /// # Panics
+            /// In debug builds this panics if ``field`` is not a write-1-to-clear field of this register.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn clear(&self, field: RegisterField<$t>) {
+                let w1c = 0 $(| $crate::define_mmio_register!(@gated_mask [$($cfg)*] $t, $offset, $bits))*;
+                debug_assert!(field.mask() & !w1c == 0, "register field is not write-1-to-clear");
+                self.0.set(field.mask());
+            }
+        }
+    };
+
+    // internal rule: the ``read_and_clear`` method of a register with read-to-clear fields
+    (@rc $t:ty, []) => {};
+    (@rc $t:ty, [$({ $rc:ident [$($cfg:tt)*] $offset:tt $bits:tt })+]) => {
+        impl<A: Readable<Width = $t>> Handle<A> {
+            /// Read the value of a read-to-clear field of the register. This performs exactly one read of the
+            /// register, which clears this field. As this read clears all read-to-clear fields of the register, the
+            /// value of the other ones is lost. Use ``get`` to read all of them at once instead.
+            ///
+            /// # Panics
+            /// In debug builds this panics if ``field`` is not a read-to-clear field of this register.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn read_and_clear(&self, field: RegisterField<$t>) -> $t {
+                let rc = 0 $(| $crate::define_mmio_register!(@gated_mask [$($cfg)*] $t, $offset, $bits))*;
+                debug_assert!(field.mask() & !rc == 0, "register field is not read-to-clear");
+                (self.0.get() & field.mask()) >> field.shift()
+            }
+        }
+    };
+
+    // internal rule: the ``write_config`` method of a register with reserved fields
+    (@reserved $t:ty, []) => {};
+    (@reserved $t:ty, [$({ $reserved:ident [$($cfg:tt)*] $offset:tt $bits:tt })+]) => {
+        impl<A: Modifiable<Width = $t>> Handle<A> {
+            /// Write the value of one or more register fields like ``write_value``, but preserve the contents of the
+            /// reserved fields. The register is read first and the bits of the reserved fields are written back
+            /// unchanged, while all other bits not covered by the value are set to 0. This is performed with the
+            /// ``rmw`` of the register access type, so the reserved bits could not change in between.
+            ///
+            /// # Panics
+            /// In debug builds this panics if ``fieldvalue`` covers bits of a reserved field.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn write_config(&self, fieldvalue: TypedFieldValue<Values, $t>) {
+                let reserved = 0 $(| $crate::define_mmio_register!(@gated_mask [$($cfg)*] $t, $offset, $bits))*;
+                debug_assert!(fieldvalue.mask() & reserved == 0, "register field value covers reserved bits");
+                let raw_val = fieldvalue.raw_value() & fieldvalue.mask() & !reserved;
+
+                self.0.rmw(|old_val| (old_val & reserved) | raw_val);
+            }
+
+            /// Udate a register field with a given value like ``modify``, but reject a field covering bits of a
+            /// reserved field. The reserved fields are written back with the contents read, so the datasheet rule
+            /// to preserve the reserved bits is enforced. If the field is known at compile time the check is
+            /// usually optimized away.
+            /// The function returns the register raw value set has been set with this update
+            ///
+            /// # Panics
+            /// This panics if ``field`` covers bits of a reserved field.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn modify_safe(&self, field: RegisterField<$t>, value: $t) -> $t {
+                let reserved = 0 $(| $crate::define_mmio_register!(@gated_mask [$($cfg)*] $t, $offset, $bits))*;
+                assert!(field.mask() & reserved == 0, "register field covers reserved bits");
+                self.0.modify(field, value)
+            }
+        }
+    };
+
+    // internal rule: the documentation of a register with read-to-clear fields
+    (@rc_doc []) => { "" };
+    (@rc_doc [$({ $rc:ident $($info:tt)* })+]) => {
+        concat!("\n\n# Hint\nReading the register clears its read-to-clear fields:", $("\n- ", stringify!($rc)),+)
+    };
+
+    // internal rule: the documentation of the field modifiers
+    (@modifier_doc W1C) => { "Writing 1 to this field clears it, writing 0 has no effect." };
+    (@modifier_doc RC) => { "Reading this field clears it." };
+    (@modifier_doc RESERVED) => { "This field is reserved, its contents need to be preserved when writing." };
+    (@modifier_doc EXHAUSTIVE) => { "Each encoding of this field is named with a specific value." };
+    (@modifier_doc HEX) => { "The raw value of this field is printed in hexadecimal notation." };
+    (@modifier_doc BIN) => { "The raw value of this field is printed in binary notation." };
+    (@modifier_doc TYPED) => { "The value of this field is provided as a type of its own by ``read_typed``." };
+
+    // internal rule: the mask of a field calculated from its offset and size at compile time
+    (@mask $t:ty, $offset:tt, $bits:tt) => {
+        // a field without bits has an empty mask instead of overflowing the shift, so only the check of the field
+        // definition reports it
+        match (!(0 as $t)).checked_shr(<$t>::BITS - $bits) {
+            Some(mask) => mask,
+            None => 0,
+        } << $offset
+    };
+
+    // internal rule: the value if all predicates of the ``cfg`` attributes of the field hold, 0 otherwise. This does
+    // not refer to the field by name, so it compiles even if the field is compiled out.
+    (@gated [$($cfg:tt)*] $value:expr) => {
+        (if cfg!(all($($cfg)*)) { $value } else { 0 })
+    };
+    (@gated_mask [$($cfg:tt)*] $t:ty, $offset:tt, $bits:tt) => {
+        $crate::define_mmio_register!(@gated [$($cfg)*] $crate::define_mmio_register!(@mask $t, $offset, $bits))
+    };
+
+    // internal rule: the fields referring to the same bits as another field. Importing the field provides the
+    // constant as well as the module of the other field and fails to compile if there is no such field.
+    (@aliases $({ [$($attr:tt)*] [$($vis:tt)*] $alias:ident $other:ident })*) => {
+        $(
+            $($attr)*
+            #[allow(unused_imports)]
+            $($vis)* use self::$other as $alias;
+        )*
+    };
+
+    // internal rule: compile time checks of the field definitions
+    (@check $name:ident, $t:ty, $(
+        { [$($attr:tt)*] [$($cfg:tt)*] [$($vis:tt)*] $field:ident $offset:tt $bits:tt [$($modifier:ident)*]
+            [$($values:tt)*] }
+    )*) => {
+        $(
+            #[cfg(all($($cfg)*))]
+            const _: () = assert!(
+                $bits >= 1,
+                concat!("the field ", stringify!($field), " of register ", stringify!($name), " covers no bits")
+            );
+            #[cfg(all($($cfg)*))]
+            #[allow(clippy::int_plus_one)]
+            const _: () = assert!(
+                $offset + $bits <= core::mem::size_of::<$t>() * 8,
+                concat!("the field ", stringify!($field), " exceeds the width of register ", stringify!($name))
+            );
+        )*
+        // if any bit is covered by more than one field the bits set in all masks are less than the sum of the bits
+        // set in each mask
+        const _: () = assert!(
+            (0 as $t $(| $crate::define_mmio_register!(@gated_mask [$($cfg)*] $t, $offset, $bits))*).count_ones()
+                == 0 $(+ ($crate::define_mmio_register!(@gated_mask [$($cfg)*] $t, $offset, $bits)).count_ones())*,
+            concat!("the fields of register ", stringify!($name), " overlap")
+        );
+        // each field name is used once within a register, fields compiled out do not count
+        $crate::define_mmio_register!(
+            @unique $name [$(if cfg!(all($($cfg)*)) { stringify!($field) } else { "" }),*]
+            $({ [$($cfg)*] $field })*
+        );
+        $(
+            $crate::define_mmio_register!(@exhaustive $field $bits [$($modifier)*] [$($values)*]);
+        )*
+    };
+
+    // internal rule: compile time check that no field name is repeated within the names of the register
+    (@unique $name:ident $names:tt $({ [$($cfg:tt)*] $field:ident })*) => {
+        $(
+            #[cfg(all($($cfg)*))]
+            const _: () = assert!(
+                $crate::count_name(stringify!($field), &$names) == 1,
+                concat!("the field ", stringify!($field), " is defined more than once in register ", stringify!($name))
+            );
+        )*
+    };
+
+    // internal rule: compile time checks that a field marked ``EXHAUSTIVE`` names each of its encodings. The
+    // encodings of fields up to 4 bits are checked one by one to name the encoding missing.
+    (@exhaustive $field:ident $bits:tt [] [$($values:tt)*]) => {};
+    (@exhaustive $field:ident $bits:tt [EXHAUSTIVE $($modifier:ident)*]
+        [$($(#[doc = $fvdoc:expr])* $enum:ident = $value:expr),*]
+    ) => {
+        const _: () = assert!(
+            [$(stringify!($enum)),*].len() == 1 << $bits,
+            concat!("the field ", stringify!($field), " does not name exactly one value for each of its encodings")
+        );
+        $crate::define_mmio_register!(@encodings $field $bits [$($value),*]);
+    };
+    (@exhaustive $field:ident $bits:tt [$other:ident $($modifier:ident)*] [$($values:tt)*]) => {
+        $crate::define_mmio_register!(@exhaustive $field $bits [$($modifier)*] [$($values)*]);
+    };
+    (@encodings $field:ident 1 [$($value:expr),*]) => {
+        $crate::define_mmio_register!(@encoding $field [$($value),*] 0b0 0b1);
+    };
+    (@encodings $field:ident 2 [$($value:expr),*]) => {
+        $crate::define_mmio_register!(@encoding $field [$($value),*] 0b00 0b01 0b10 0b11);
+    };
+    (@encodings $field:ident 3 [$($value:expr),*]) => {
+        $crate::define_mmio_register!(
+            @encoding $field [$($value),*] 0b000 0b001 0b010 0b011 0b100 0b101 0b110 0b111
+        );
+    };
+    (@encodings $field:ident 4 [$($value:expr),*]) => {
+        $crate::define_mmio_register!(
+            @encoding $field [$($value),*] 0b0000 0b0001 0b0010 0b0011 0b0100 0b0101 0b0110 0b0111
+            0b1000 0b1001 0b1010 0b1011 0b1100 0b1101 0b1110 0b1111
+        );
+    };
+    (@encodings $field:ident $bits:tt [$($value:expr),*]) => {
+        const _: () = assert!(
+            $crate::names_all_encodings(&[$($value as u64),*], $bits),
+            concat!("the field ", stringify!($field), " does not name each of its encodings")
+        );
+    };
+    (@encoding $field:ident [$($value:expr),*]) => {};
+    (@encoding $field:ident [$($value:expr),*] $encoding:literal $($rest:literal)*) => {
+        const _: () = assert!(
+            false $(|| $value == $encoding)*,
+            concat!("the field ", stringify!($field), " does not name the encoding ", stringify!($encoding))
+        );
+        $crate::define_mmio_register!(@encoding $field [$($value),*] $($rest)*);
+    };
+
+    // internal rule: FIELD OFFSET(num) BITS(num) [ VALUE: val ] of a register with TYPE
+    (@fields $t:ty, $(
+        { [$($attr:tt)*] [$($cfg:tt)*] [$($vis:tt)*] $field:ident $offset:tt $bits:tt [$($modifier:ident)*]
+            [$($values:tt)*] }
+    )*) => {
+        $(
+            $($attr)*
+            #[allow(unused_variables, dead_code)]
+            $($vis)* const $field: RegisterField<$t> =
+                RegisterField::<$t>::new($crate::define_mmio_register!(@mask $t, 0, $bits), $offset);
+            $crate::define_mmio_register!(@typed $t, [$($attr)*] [$($vis)*] $field [$($modifier)*]);
+            $($attr)*
+            $(#[doc = $crate::define_mmio_register!(@modifier_doc $modifier)])*
+            $($vis)* mod $field {
+                use super::*;
+                /// The mask of the bits the field covers within the register
+                #[allow(dead_code)]
+                pub const MASK: $t = $crate::define_mmio_register!(@mask $t, $offset, $bits);
+
+                /// The position of the lowest bit of the field within the register
+                #[allow(dead_code)]
+                pub const SHIFT: u32 = $offset;
+
+                /// The number of bits the field covers
+                #[allow(dead_code)]
+                pub const WIDTH: u32 = $bits;
+
+                /// Create a ``TypedFieldValue`` bound to this ``Register`` from the current ``RegisterField``
+                /// and a given value
+                #[inline]
+                #[allow(unused_variables, dead_code)]
+                pub const fn with_value(value: $t) -> TypedFieldValue<Values, $t> {
+                    TypedFieldValue::<Values, $t>::new(RegisterFieldValue::<$t>::new($field, value))
+                }
+
+                /// The names and values of the specific values defined for this field
+                #[allow(dead_code)]
+                pub const VARIANTS: &[(&str, $t)] = $crate::define_mmio_register!(@variants $($values)*);
+
+                /// Provide the name of the specific value defined for this field that matches the raw field value
+                #[inline]
+                #[allow(dead_code)]
+                pub fn name_of(raw: $t) -> Option<&'static str> {
+                    VARIANTS.iter().find(|(_, value)| *value == raw).map(|(name, _)| *name)
+                }
+                $crate::define_mmio_register!(@values $field, $t, $($values)*);
+            }
+        )*
+    };
+
+    // internal rule: the value type of a field marked with ``TYPED``
+    (@typed $t:ty, [$($attr:tt)*] [$($vis:tt)*] $field:ident []) => {};
+    (@typed $t:ty, [$($attr:tt)*] [$($vis:tt)*] $field:ident [TYPED $($modifier:ident)*]) => {
+        $crate::paste::paste! {
+            $($attr)*
+            #[doc = concat!("The value of the field ``", stringify!($field), "`` as a type of its own")]
+            #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            $($vis)* struct [<$field:camel Value>](pub $t);
+
+            impl TypedField for [<$field:camel Value>] {
+                type Width = $t;
+                type Register = Values;
+                const FIELD: RegisterField<$t> = $field;
+
+                #[inline]
+                fn from_raw(raw: $t) -> Self {
+                    Self(raw)
+                }
+
+                #[inline]
+                fn raw(self) -> $t {
+                    self.0
+                }
+            }
+
+            impl core::ops::Deref for [<$field:camel Value>] {
+                type Target = $t;
+
+                #[inline]
+                fn deref(&self) -> &$t {
+                    &self.0
+                }
+            }
+
+            impl From<[<$field:camel Value>]> for $t {
+                #[inline]
+                fn from(value: [<$field:camel Value>]) -> $t {
+                    value.0
+                }
+            }
+        }
+    };
+    (@typed $t:ty, [$($attr:tt)*] [$($vis:tt)*] $field:ident [$other:ident $($modifier:ident)*]) => {
+        $crate::define_mmio_register!(@typed $t, [$($attr)*] [$($vis)*] $field [$($modifier)*]);
+    };
+
+    // internal rule: the specific values defined for a field
+    (@values $field:ident, $t:ty,) => {};
+    (@values $field:ident, $t:ty, $($(#[doc = $fvdoc:expr])* $enum:ident = $value:expr),+) => {
+        $(
+            $(#[doc = $fvdoc])*
+            #[allow(unused_variables, dead_code)]
+            pub const $enum: TypedFieldValue<Values, $t> =
+                TypedFieldValue::<Values, $t>::new(RegisterFieldValue::<$t>::new($field, $value));
+        )+
+
+        /// The specific values defined for this field as enum to be matched on. It is converted from the raw value
+        /// of the field with ``try_from``, that fails with the raw value if it does not match any of them.
+        #[allow(non_camel_case_types, dead_code)]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum Value {
+            $(
+                $(#[doc = $fvdoc])*
+                $enum,
+            )+
+        }
+
+        impl core::convert::TryFrom<$t> for Value {
+            type Error = $t;
+
+            fn try_from(raw: $t) -> Result<Self, $t> {
+                $(
+                    if raw == $value {
+                        return Ok(Value::$enum);
+                    }
+                )+
+                Err(raw)
+            }
+        }
+
+        impl From<Value> for $t {
+            fn from(value: Value) -> $t {
+                match value {
+                    $(Value::$enum => $value,)+
+                }
+            }
+        }
+
+        impl From<Value> for TypedFieldValue<Values, $t> {
+            fn from(value: Value) -> TypedFieldValue<Values, $t> {
+                match value {
+                    $(Value::$enum => $enum,)+
+                }
+            }
+        }
+
+        impl From<Value> for RegisterFieldValue<$t> {
+            fn from(value: Value) -> RegisterFieldValue<$t> {
+                TypedFieldValue::<Values, $t>::from(value).value()
+            }
+        }
+    };
+}
+
+/// Macro to define an array of identical MMIO registers. The registers are located at ``BASE + index * STRIDE`` for
+/// each index in the range ``0..COUNT``. All registers of the array share the same field definitions.<br>
+/// The access mode and the register size/width are the same as for [define_mmio_register].
+///
+/// # Example
+///
+/// Define the 16 control registers of the DMA channels located 0x100 bytes apart
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register_array!(
+///     /// DMA channel control and status register
+///     DMA_CS<ReadWrite<u32>@(0x3F00_7000, 0x100, 16)> {
+///         ACTIVE OFFSET(0),
+///         END OFFSET(1),
+///         PRIORITY OFFSET(16) BITS(4)
+///     }
+/// );
+///
+/// fn main() {
+///     // activate DMA channel 3
+///     DMA_CS::Register.at(3).modify(DMA_CS::ACTIVE, 1);
+///     // check if channel 5 has finished its transfer
+///     let done = DMA_CS::Register.at(5).read(DMA_CS::END);
+///     // lower the priority of all channels
+///     for channel in DMA_CS::Register.iter() {
+///         channel.modify(DMA_CS::PRIORITY, 0);
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! define_mmio_register_array {
+    // REGISTER_NAME<ReadWrite<TYPE>@(BASE, STRIDE, COUNT) = RESET> { FIELD OFFSET(num) BITS(num) [ VALUE: val ] }
+    ($($(#[doc = $rdoc:expr])* $vis:vis $name:ident<
+        $access:ident<$t:ty>@($base:expr, $stride:expr, $count:expr) $(= $reset:literal)?
+    > $(
+        { $($fields:tt)* }
+    )?),*) => {
+        $(
+            $crate::define_mmio_register!(
+                @parse define_mmio_register_array
+                { $(#[doc = $rdoc])* $vis $name, $access, $t, $base, $stride, $count, [$($reset)?] }
+                $($($fields)*)?
+            );
+        )*
+    };
+
+    // internal rule: the register array module containing the register array and its fields
+    (@register {
+        $(#[doc = $rdoc:expr])* $vis:vis $name:ident, $access:ident, $t:ty, $base:expr, $stride:expr, $count:expr,
+        [$($reset:literal)?] $(, { [$($aattr:tt)*] [$($avis:tt)*] $alias:ident $other:ident })*
+    } { [$($w1c:tt)*] [$($rc:tt)*] [$($reserved:tt)*] } $($field:tt)*) => {
+        #[allow(non_snake_case)]
+        #[allow(non_upper_case_globals)]
+        #[allow(deprecated)]
+        $vis mod $name {
+            #[allow(unused_imports)]
+            use $crate::*;
+            use super::*;
+            /// The number of registers contained in this register array
+            #[allow(dead_code)]
+            pub const COUNT: usize = $count;
+
+            $crate::define_mmio_register!(@handle $name, $t, [$($rc)*], $($field)*);
+            $crate::define_mmio_register!(@methods $t, [$($w1c)*] [$($rc)*] [$($reserved)*], $($field)*);
+            $crate::define_mmio_register!(@reset $t, $($reset)?);
+
+            /// Accessor to the registers of this register array
+            #[derive(Clone, Debug)]
+            pub struct RegisterArray;
+
+            impl RegisterArray {
+                /// Provide the access to the register at the given ``index`` of this register array
+                #[inline]
+                #[allow(dead_code)]
+                pub fn at(&self, index: usize) -> Handle<$access<$t>> {
+                    debug_assert!(index < COUNT, "register array index out of range");
+                    Handle($access::<$t>::new($base + index * $stride))
+                }
+
+                /// Provide an iterator over the accesses to all registers of this register array in the order of
+                /// their index
+                #[inline]
+                #[allow(dead_code)]
+                pub fn iter(&self) -> RegisterArrayIter {
+                    RegisterArrayIter { indices: 0..COUNT }
+                }
+            }
+
+            /// Iterator over the accesses to the registers of this register array
+            #[derive(Clone, Debug)]
+            pub struct RegisterArrayIter {
+                indices: core::ops::Range<usize>,
+            }
+
+            impl Iterator for RegisterArrayIter {
+                type Item = Handle<$access<$t>>;
+
+                #[inline]
+                fn next(&mut self) -> Option<Self::Item> {
+                    self.indices.next().map(|index| Register.at(index))
+                }
+
+                #[inline]
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    self.indices.size_hint()
+                }
+            }
+
+            impl DoubleEndedIterator for RegisterArrayIter {
+                #[inline]
+                fn next_back(&mut self) -> Option<Self::Item> {
+                    self.indices.next_back().map(|index| Register.at(index))
+                }
+            }
+
+            impl ExactSizeIterator for RegisterArrayIter {}
+
+            $(#[doc = $rdoc])*
+            #[allow(unused_variables, dead_code)]
+            pub const Register: RegisterArray = RegisterArray;
+            $crate::define_mmio_register!(@aligned $name, $access, $t, "address" $base);
+            $crate::define_mmio_register!(@aligned $name, $access, $t, "stride" $stride);
+            $crate::define_mmio_register!(@fields $t, $($field)*);
+            $crate::define_mmio_register!(@aliases $({ [$($aattr)*] [$($avis)*] $alias $other })*);
+            $crate::define_mmio_register!(@check $name, $t, $($field)*);
+        }
+    };
+}
+
+/// Macro to define the MMIO registers of a peripheral located at the same base address. Each register is given with
+/// its offset to the base address, matching the offset column of the datasheet. The macro defines a module for the
+/// peripheral, that contains the base address as ``BASE`` and a module for each register like [define_mmio_register].
+/// The access mode, the register size/width, the reset value and the fields are defined the same way.
+///
+/// # Example
+///
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_peripheral!(
+///     /// The PL011 UART
+///     UART0 @ 0x3F20_1000 {
+///         /// Data register
+///         DR @ 0x00 <ReadWrite<u32>> {
+///             DATA OFFSET(0) BITS(8)
+///         },
+///         /// Flag register
+///         FR @ 0x18 <ReadOnly<u32> = 0x90> {
+///             BUSY OFFSET(3),
+///             TXFF OFFSET(5)
+///         }
+///     }
+/// );
+///
+/// fn main() {
+///     while UART0::FR::Register.is_set(UART0::FR::TXFF) {}
+///     UART0::DR::Register.write(UART0::DR::DATA, b'A' as u32);
+/// }
+/// ```
+///
+/// If the base address of the peripheral is only known at runtime it is given as a [PeripheralBase] with
+/// ``@dyn(BASE)``. The registers are then provided the same way as with ``@dyn(BASE + OFFSET)`` of
+/// [define_mmio_register].
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// static UART0_BASE: PeripheralBase = PeripheralBase::new(0x3F20_1000);
+///
+/// define_mmio_peripheral!(
+///     UART0 @dyn(UART0_BASE) {
+///         DR @ 0x00 <ReadWrite<u32>> {
+///             DATA OFFSET(0) BITS(8)
+///         }
+///     }
+/// );
+///
+/// fn main() {
+///     UART0_BASE.set_base(0xFE20_1000);
+///     UART0::DR::Register().write(UART0::DR::DATA, b'A' as u32);
+/// }
+/// ```
+///
+/// If the base address depends on the SoC the driver is built for, the alternative base addresses are given with
+/// ``@ cfg(SOC => BASE, _ => BASE)``. The first alternative whose cargo feature is enabled for the crate using the
+/// macro provides the base address, the one given with ``_`` is used if none of them is enabled. This allows a
+/// single driver crate to support different Raspberry Pi models by features declared in its own ``Cargo.toml``.
+/// The SoC names ``bcm2835``, ``bcm2836``, ``bcm2837`` and ``bcm2711`` select the feature of the same name. Any
+/// other feature is given by its name as string literal, e.g. ``"pi4" => 0xFE20_1000``.
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_peripheral!(
+///     UART0 @ cfg(bcm2711 => 0xFE20_1000, _ => 0x3F20_1000) {
+///         DR @ 0x00 <ReadWrite<u32>> {
+///             DATA OFFSET(0) BITS(8)
+///         }
+///     }
+/// );
+///
+/// fn main() {
+///     UART0::DR::Register.write(UART0::DR::DATA, b'A' as u32);
+/// }
+/// ```
+#[macro_export]
+macro_rules! define_mmio_peripheral {
+    // PERIPHERAL_NAME @ BASE { REGISTER_NAME @ OFFSET <ReadWrite<TYPE> = RESET> { FIELD OFFSET(num) BITS(num) } }
+    ($(#[doc = $pdoc:expr])* $vis:vis $name:ident @ $base:literal { $($registers:tt)* }) => {
+        $(#[doc = $pdoc])*
+        #[allow(non_snake_case)]
+        $vis mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            /// The base address of the peripheral
+            #[allow(dead_code)]
+            pub const BASE: usize = $base;
+
+            $crate::define_mmio_peripheral!(@registers [] BASE [] $($registers)*);
+        }
+    };
+
+    // PERIPHERAL_NAME @ cfg(SOC => BASE, _ => BASE) { REGISTER_NAME @ OFFSET <ReadWrite<TYPE> = RESET> { ... } }
+    ($(#[doc = $pdoc:expr])* $vis:vis $name:ident @ cfg($($soc:tt => $socbase:literal),+ $(,)?) {
+        $($registers:tt)*
+    }) => {
+        $(#[doc = $pdoc])*
+        #[allow(non_snake_case)]
+        $vis mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            /// The base address of the peripheral on the SoC the crate is built for
+            #[allow(dead_code)]
+            pub const BASE: usize = $crate::define_mmio_peripheral!(@select $($soc => $socbase),+);
+
+            $crate::define_mmio_peripheral!(@registers [] BASE [] $($registers)*);
+        }
+    };
+
+    // PERIPHERAL_NAME @dyn(BASE) { REGISTER_NAME @ OFFSET <ReadWrite<TYPE> = RESET> { FIELD OFFSET(num) BITS(num) } }
+    ($(#[doc = $pdoc:expr])* $vis:vis $name:ident @dyn($base:ident) { $($registers:tt)* }) => {
+        $(#[doc = $pdoc])*
+        #[allow(non_snake_case)]
+        $vis mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            $crate::define_mmio_peripheral!(@registers [dyn] $base [] $($registers)*);
+        }
+    };
+
+    // internal rule: each register at its offset to the base address of the peripheral. The registers are collected
+    // into the ``REGISTERS`` table printed with ``dump_all!``.
+    (@registers [$($kind:ident)?] $base:ident [$($dump:tt)*]) => {
+        /// The registers of the peripheral as printed with [dump_all]
+        #[allow(dead_code)]
+        pub const REGISTERS: &[$crate::debug::RegisterDump] = &[$($dump)*];
+    };
+    (@registers [$($kind:ident)?] $base:ident [$($dump:tt)*]
+        $(#[doc = $rdoc:expr])* $reg:ident @ $offset:literal <$access:ident<$t:ty>> $({ $($fields:tt)* })?
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::define_mmio_register!(
+            $(#[doc = $rdoc])* pub $reg<$access<$t>@$($kind)?($base + $offset)> $({ $($fields)* })?
+        );
+        $crate::define_mmio_peripheral!(
+            @registers [$($kind)?] $base [$($dump)* $crate::define_mmio_peripheral!(@dump [$($kind)?] $reg $access),]
+            $($($rest)*)?
+        );
+    };
+    (@registers [$($kind:ident)?] $base:ident [$($dump:tt)*]
+        $(#[doc = $rdoc:expr])* $reg:ident @ $offset:literal <$access:ident<$t:ty> = $reset:literal>
+        $({ $($fields:tt)* })? $(, $($rest:tt)*)?
+    ) => {
+        $crate::define_mmio_register!(
+            $(#[doc = $rdoc])* pub $reg<$access<$t>@$($kind)?($base + $offset) = $reset> $({ $($fields)* })?
+        );
+        $crate::define_mmio_peripheral!(
+            @registers [$($kind)?] $base [$($dump)* $crate::define_mmio_peripheral!(@dump [$($kind)?] $reg $access),]
+            $($($rest)*)?
+        );
+    };
+
+    // internal rule: the base address of the first SoC alternative whose feature is enabled. The last alternative
+    // given with ``_`` is used if none of them is enabled.
+    (@select _ => $base:literal) => {
+        $base
+    };
+    (@select $soc:tt => $socbase:literal $(, $($rest:tt)*)?) => {
+        if $crate::define_mmio_peripheral!(@feature $soc) {
+            $socbase
+        } else {
+            $crate::define_mmio_peripheral!(@select $($($rest)*)?)
+        }
+    };
+    (@select) => {
+        compile_error!("the base address used if no SoC alternative is selected needs to be given last with _")
+    };
+
+    // internal rule: whether the feature selecting a SoC alternative is enabled
+    (@feature bcm2835) => {
+        cfg!(feature = "bcm2835")
+    };
+    (@feature bcm2836) => {
+        cfg!(feature = "bcm2836")
+    };
+    (@feature bcm2837) => {
+        cfg!(feature = "bcm2837")
+    };
+    (@feature bcm2711) => {
+        cfg!(feature = "bcm2711")
+    };
+    (@feature $feature:literal) => {
+        cfg!(feature = $feature)
+    };
+    (@feature $other:ident) => {
+        compile_error!(concat!(
+            "unknown SoC ", stringify!($other), ", give the name of the feature selecting it as string literal"
+        ))
+    };
+
+    // internal rule: the entry of a register in the ``REGISTERS`` table
+    (@dump [$($kind:ident)?] $reg:ident WriteOnly) => {
+        $crate::debug::RegisterDump { name: stringify!($reg), dump: None }
+    };
+    (@dump [$($kind:ident)?] $reg:ident WriteOnlyBE) => {
+        $crate::debug::RegisterDump { name: stringify!($reg), dump: None }
+    };
+    (@dump [] $reg:ident $access:ident) => {
+        $crate::debug::RegisterDump {
+            name: stringify!($reg),
+            dump: Some(|f| core::fmt::Debug::fmt(&$reg::Register, f)),
+        }
+    };
+    (@dump [dyn] $reg:ident $access:ident) => {
+        $crate::debug::RegisterDump {
+            name: stringify!($reg),
+            dump: Some(|f| core::fmt::Debug::fmt(&$reg::Register(), f)),
+        }
+    };
+}
+
+/// Macro to print all registers of a peripheral defined with [define_mmio_peripheral]. It provides the peripheral
+/// with the decoded contents of each register as its debug output. Each register is read when printed, so the output
+/// reflects the state of the device at that time. This helps to compare the device state against a known-good one
+/// during bring-up. Write only registers could not be read and are printed as such. Registers with read-to-clear
+/// fields are not read either, as printing them would clear those fields, and are printed as ``<read clears>``.
+///
+/// # Example
+///
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_peripheral!(
+///     UART0 @ 0x3F20_1000 {
+///         DR @ 0x00 <ReadWrite<u32>> {
+///             DATA OFFSET(0) BITS(8)
+///         },
+///         FR @ 0x18 <ReadOnly<u32>> {
+///             BUSY OFFSET(3),
+///             TXFF OFFSET(5)
+///         }
+///     }
+/// );
+///
+/// fn main() {
+///     let dump = dump_all!(UART0);
+///     // print the registers to the console of choice, e.g. with
+///     // writeln!(console, "{:#?}", dump);
+/// }
+/// ```
+#[macro_export]
+macro_rules! dump_all {
+    ($peripheral:ident $(:: $path:ident)*) => {
+        $crate::debug::PeripheralDump::new(
+            concat!(stringify!($peripheral) $(, "::", stringify!($path))*),
+            $peripheral $(:: $path)*::REGISTERS,
+        )
+    };
+}
+
+/// Macro to branch on the specific value a register field contains. The field is read once and its value is matched
+/// against the specific values defined for this field. As the hardware may provide a value that is not defined, the
+/// default arm ``_`` is required.
+///
+/// # Example
+///
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         BAL OFFSET(4) BITS(2) [
+///             VAL1 = 0b01,
+///             VAL2 = 0b10
 ///         ]
 ///     }
 /// );
+///
+/// fn main() {
+///     let speed = match_field!(FOO::Register, BAL {
+///         VAL1 => 100,
+///         VAL2 => 200,
+///         _ => 0,
+///     });
+/// }
 /// ```
+#[macro_export]
+macro_rules! match_field {
+    // internal rule: split the path of the register module from the register
+    (@path [$($module:tt)*] Register, $field:ident {
+        $($value:ident => $arm:expr,)+ _ => $default:expr $(,)?
+    }) => {{
+        #[allow(unused_imports)]
+        use $($module)* $field as __field;
+        match core::convert::TryFrom::try_from($($module)* Register.read(__field)) {
+            $(Ok(__field::Value::$value) => $arm,)+
+            Ok(_) | Err(_) => $default,
+        }
+    }};
+    (@path [$($module:tt)*] $segment:ident :: $($rest:tt)+) => {
+        $crate::match_field!(@path [$($module)* $segment ::] $($rest)+)
+    };
+
+    // REGISTER_MODULE::Register, FIELD { VALUE => expression, _ => expression }
+    ($($register:tt)+) => {
+        $crate::match_field!(@path [] $($register)+)
+    };
+}
+
+/// Macro combining one or more field values of the same register into one field value covering all of them. In
+/// contrast to the ``|`` operator the macro is usable in ``const`` context, so the configuration of a register can be
+/// precomputed at compile time.
+///
+/// # Example
 ///
-/// Define a MMIO register where one field has defined specific values to be choosen from when
-/// writing to or updating this specific register field
 /// ```no_run
 /// # use ruspiro_mmio_register::*;
 /// define_mmio_register!(
-///     /// A MMIO Register FOO
-///     /// Defines as Read/Write register
 ///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
-///         /// This is a register field BAR
-///         BAR OFFSET(3) BITS(3),
-///         /// This is a register field BAZ.
-///         /// It contains enum like field value definitions
-///         BAZ OFFSET(6) BITS(3) [
-///             /// This is a value of the register field
-///             VAL1 = 0b000,
-///             VAL2 = 0b010
+///         BAZ OFFSET(0) BITS(2) [
+///             VAL1 = 0b01
 ///         ],
-///         BAL OFFSET(9) BITS(2) [
-///             VAL1 = 0b01,
-///             VAL2 = 0b11
+///         BAL OFFSET(4) BITS(2) [
+///             VAL2 = 0b10
 ///         ]
 ///     }
 /// );
 ///
+/// const CONFIG: TypedFieldValue<FOO::Values, u32> = field_values!(FOO::BAZ::VAL1, FOO::BAL::VAL2);
+/// static CONFIG_RAW: u32 = FOO::pack(&[FOO::BAZ::VAL1, FOO::BAL::VAL2]);
+///
 /// fn main() {
-///     // write a specific value for one field of the MMIO register
-///     FOO::Register.write_value(
-///         FOO::BAL::VAL1
-///     );
+///     FOO::Register.write_value(CONFIG);
+///     FOO::Register.set(CONFIG_RAW);
+/// }
+/// ```
+#[macro_export]
+macro_rules! field_values {
+    ($first:expr $(, $rest:expr)* $(,)?) => {
+        $first $(.combine($rest))*
+    };
+}
+
+/// Macro asserting two snapshots of a register contain the same field values, e.g. comparing the register contents
+/// after running a driver with the expected ones in a test. Only the bits of the fields are compared, the bits not
+/// covered by any field and reserved fields are ignored. On a mismatch the panic message shows only the fields that
+/// differ, with the value of the left snapshot followed by the value of the right one.
 ///
-///     // combine field values of different fields to update the MMIO register
-///     FOO::Register.write_value(
-///         FOO::BAZ::VAL1 | FOO::BAL::VAL2
-///     );
+/// # Example
 ///
-///     // write a specific value to a register field that does not provide default/enum values
-///     FOO::Register.write_value(
-///         FOO::BAR::with_value(0b010)
-///     );
+/// ```no_run
+/// # use ruspiro_mmio_register::*;
+/// define_mmio_register!(
+///     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+///         ENABLE OFFSET(0),
+///         LEVEL OFFSET(4) BITS(4)
+///     }
+/// );
+///
+/// fn main() {
+///     // ... run the driver code
+///     let expected = FOO::Values::default().with_enable(1).with_level(0b1010);
+///     // panics e.g. with the diff FOO { LEVEL: 0b1000 -> 0b1010 }
+///     assert_fields_eq!(FOO::Register.read_all(), expected);
 /// }
 /// ```
 #[macro_export]
-macro_rules! define_mmio_register {
-    // REGISTER_NAME<ReadWrite<TYPE>@ADDRESS> { FIELD OFFSET(num) BITS(num) [ VALUE: val ] }
-    ($($(#[doc = $rdoc:expr])* $vis:vis $name:ident<$access:ident<$t:ty>@($addr:expr)> $(
-        { $(
-                $(#[doc = $fdoc:expr])*
-                $field:ident OFFSET($offset:literal) $(BITS($bits:literal))?
-                $([$($(#[doc = $fvdoc:expr])* $enum:ident = $value:expr),*])?
-        ),* }
-    )?),*) => {
-        $(
-            #[allow(non_snake_case)]
-            #[allow(non_upper_case_globals)]
-            $vis mod $name {
-                #[allow(unused_imports)]
-                use $crate::*;
-                use super::*;
-                $(#[doc = $rdoc])*
-                #[allow(unused_variables, dead_code)]
-                pub const Register: $access<$t> = $access::<$t>::new($addr);
-                $(
-                    $(
-                        $(#[doc = $fdoc])*
-                        $crate::register_field!($t, $field, $offset $(, $bits)?);
-                        pub mod $field {
-                            use super::*;
-                            /// Create a ``RegisterFieldValue`` from the current ``RegisterField``
-                            /// of this ``Register`` from a given value
-                            #[inline]
-                            #[allow(unused_variables, dead_code)]
-                            pub const fn with_value(value: $t) -> RegisterFieldValue<$t> {
-                                RegisterFieldValue::<$t>::new($field, value)
-                            }
-                            $(
-                                $crate::register_field_values!($field, $t, $($($fvdoc)*, $enum = $value),*);
-                            )*
-                        }
-                    )*
-                )*
+macro_rules! assert_fields_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if left != right {
+                    panic!(
+                        "assertion failed: `(left == right)`, the register fields differ: {:?}",
+                        left.diff_debug(right)
+                    );
+                }
             }
-        )*
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if left != right {
+                    panic!(
+                        "assertion failed: `(left == right)`, the register fields differ: {:?}: {}",
+                        left.diff_debug(right),
+                        format_args!($($arg)+)
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Emit the given items only if the ``defmt`` feature of this crate is enabled. The register definition macros expand
+/// within the crate using them, so the feature needs to be checked here.
+#[cfg(feature = "defmt")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __defmt_impl {
+    ($($item:item)*) => {
+        $($item)*
     };
 }
+
+#[cfg(not(feature = "defmt"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __defmt_impl {
+    ($($item:item)*) => {};
+}
@@ -0,0 +1,200 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Mocked MMIO address space
+//!
+//! With the ``mock`` feature enabled the register access functions do not touch the memory at the register address.
+//! They read from and write to a backing store that is allocated for each address region installed with
+//! [install_region]. This allows the same driver code to be build and unit tested on the host machine.
+//!
+//! The number of register reads from an address is provided by [reads] and the number of register writes to it by
+//! [writes]. This allows to verify that accessing registers which change on read, like read-to-clear fields, does not
+//! read them more often than expected, or that updating several fields at once performs a single read and write.
+//!
+//! As the mocked address space is shared by all threads, tests running in parallel should use distinct address
+//! regions.
+//!
+//! ```
+//! # use ruspiro_mmio_register::*;
+//! define_mmio_register!(
+//!     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+//!         BAR OFFSET(0),
+//!         BAZ OFFSET(1) BITS(3)
+//!     }
+//! );
+//!
+//! fn main() {
+//!     mock::install_region(0x3F20_0000, 0x10);
+//!
+//!     FOO::Register.write(FOO::BAZ, 0b101);
+//!     assert_eq!(mock::peek::<u32>(0x3F20_0000), 0b1010);
+//!
+//!     mock::poke::<u32>(0x3F20_0000, 0b1);
+//!     assert_eq!(FOO::Register.read(FOO::BAR), 1);
+//! }
+//! ```
+
+use crate::RegisterType;
+use core::cell::UnsafeCell;
+use core::mem::size_of;
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::vec::Vec;
+
+/// A memory region of the mocked address space
+struct Region {
+    base: usize,
+    memory: Vec<u8>,
+    reads: Vec<usize>,
+    writes: Vec<usize>,
+}
+
+/// The mocked address space is a list of regions guarded by a simple spin lock
+struct AddressSpace {
+    lock: AtomicBool,
+    regions: UnsafeCell<Vec<Region>>,
+}
+
+// the regions are only accessed while holding the lock
+unsafe impl Sync for AddressSpace {}
+
+static ADDRESS_SPACE: AddressSpace = AddressSpace {
+    lock: AtomicBool::new(false),
+    regions: UnsafeCell::new(Vec::new()),
+};
+
+/// Release the address space lock once dropped, even if the access panics
+struct Guard<'a>(&'a AtomicBool);
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+impl AddressSpace {
+    fn with<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut Vec<Region>) -> R,
+    {
+        while self
+            .lock
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+        let _guard = Guard(&self.lock);
+        f(unsafe { &mut *self.regions.get() })
+    }
+}
+
+/// Install a region of ``len`` bytes starting at address ``base`` in the mocked address space. All register accesses
+/// need to fall into an installed region. The memory of the region is initialized with 0. Installing a region with
+/// the same base address again resets its contents.
+pub fn install_region(base: usize, len: usize) {
+    ADDRESS_SPACE.with(|regions| {
+        regions.retain(|region| region.base != base);
+        regions.push(Region {
+            base,
+            memory: std::vec![0; len],
+            reads: std::vec![0; len],
+            writes: std::vec![0; len],
+        });
+    });
+}
+
+/// Read the value of type ``T`` currently stored at address ``addr`` of the mocked address space.
+///
+/// # Panics
+/// The function panics if ``addr`` is not covered by an installed region.
+pub fn peek<T: RegisterType>(addr: usize) -> T {
+    ADDRESS_SPACE.with(|regions| {
+        let cell = memory_of::<T>(regions, addr);
+        unsafe { (cell as *const T).read_unaligned() }
+    })
+}
+
+/// Store the value of type ``T`` at address ``addr`` of the mocked address space.
+///
+/// # Panics
+/// The function panics if ``addr`` is not covered by an installed region.
+pub fn poke<T: RegisterType>(addr: usize, value: T) {
+    ADDRESS_SPACE.with(|regions| {
+        let cell = memory_of::<T>(regions, addr);
+        unsafe { (cell as *mut T).write_unaligned(value) }
+    })
+}
+
+/// The number of register reads from address ``addr`` since the region containing it has been installed. Reads with
+/// [peek] are not counted.
+///
+/// # Panics
+/// The function panics if ``addr`` is not covered by an installed region.
+pub fn reads(addr: usize) -> usize {
+    ADDRESS_SPACE.with(|regions| *counters_of(regions, addr).0)
+}
+
+/// The number of register writes to address ``addr`` since the region containing it has been installed. Writes with
+/// [poke] are not counted.
+///
+/// # Panics
+/// The function panics if ``addr`` is not covered by an installed region.
+pub fn writes(addr: usize) -> usize {
+    ADDRESS_SPACE.with(|regions| *counters_of(regions, addr).1)
+}
+
+/// Read the value of type ``T`` stored at address ``addr`` on behalf of a register read. This is what the register
+/// reads are mapped to.
+pub(crate) fn read<T: RegisterType>(addr: usize) -> T {
+    ADDRESS_SPACE.with(|regions| {
+        *counters_of(regions, addr).0 += 1;
+        let cell = memory_of::<T>(regions, addr);
+        unsafe { (cell as *const T).read_unaligned() }
+    })
+}
+
+/// Store the value of type ``T`` at address ``addr`` on behalf of a register write. This is what the register writes
+/// are mapped to.
+pub(crate) fn write<T: RegisterType>(addr: usize, value: T) {
+    ADDRESS_SPACE.with(|regions| {
+        *counters_of(regions, addr).1 += 1;
+        let cell = memory_of::<T>(regions, addr);
+        unsafe { (cell as *mut T).write_unaligned(value) }
+    })
+}
+
+/// Update the value stored at address ``addr`` with the result of ``f`` while holding the address space lock. This is
+/// what the atomic register accesses are mapped to. Returns the previous value.
+pub(crate) fn fetch_update<T: RegisterType>(addr: usize, f: impl FnOnce(T) -> T) -> T {
+    ADDRESS_SPACE.with(|regions| {
+        let (reads, writes) = counters_of(regions, addr);
+        *reads += 1;
+        *writes += 1;
+        let cell = memory_of::<T>(regions, addr) as *mut T;
+        let old_val = unsafe { cell.read_unaligned() };
+        unsafe { cell.write_unaligned(f(old_val)) };
+        old_val
+    })
+}
+
+fn region_of(regions: &mut [Region], addr: usize, size: usize) -> &mut Region {
+    regions
+        .iter_mut()
+        .find(|region| addr >= region.base && addr + size <= region.base + region.memory.len())
+        .unwrap_or_else(|| panic!("no mock region installed for address {:#x}", addr))
+}
+
+fn memory_of<T>(regions: &mut [Region], addr: usize) -> *mut u8 {
+    let region = region_of(regions, addr, size_of::<T>());
+    unsafe { region.memory.as_mut_ptr().add(addr - region.base) }
+}
+
+fn counters_of(regions: &mut [Region], addr: usize) -> (&mut usize, &mut usize) {
+    let region = region_of(regions, addr, 1);
+    let offset = addr - region.base;
+    (&mut region.reads[offset], &mut region.writes[offset])
+}
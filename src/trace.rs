@@ -0,0 +1,69 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! # Register access tracing
+//!
+//! With the ``trace`` feature enabled every register access invokes the hook installed with [set_hook]. The hook is
+//! called with the kind of the access, the register address and the value read from or written to the register. This
+//! allows to log the register accesses e.g. to the UART or to record them for later comparison.
+//!
+//! ```no_run
+//! # use ruspiro_mmio_register::*;
+//! define_mmio_register!(
+//!     FOO<ReadWrite<u32>@(0x3F20_0000)> {
+//!         BAR OFFSET(0)
+//!     }
+//! );
+//!
+//! fn log_access(kind: trace::AccessKind, addr: usize, value: u64) {
+//!     // print the access to the console of choice
+//! }
+//!
+//! fn main() {
+//!     trace::set_hook(log_access);
+//!     // calls the hook with the value read and the value written
+//!     FOO::Register.modify(FOO::BAR, 1);
+//! }
+//! ```
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// The kind of a register access passed to the hook
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessKind {
+    /// The value has been read from the register
+    Read,
+    /// The value is written to the register
+    Write,
+}
+
+/// The hook called on each register access with the kind of the access, the register address and the value
+pub type Hook = fn(AccessKind, usize, u64);
+
+/// The hook installed stored as raw function pointer, 0 if none is installed
+static HOOK: AtomicUsize = AtomicUsize::new(0);
+
+/// Install the hook called on each register access. This replaces any hook installed before.
+pub fn set_hook(hook: Hook) {
+    HOOK.store(hook as usize, Ordering::Release);
+}
+
+/// Remove the hook installed, so the register accesses are no longer traced.
+pub fn clear_hook() {
+    HOOK.store(0, Ordering::Release);
+}
+
+/// Call the hook installed for a register access.
+#[inline]
+pub(crate) fn call(kind: AccessKind, addr: usize, value: u64) {
+    let raw = HOOK.load(Ordering::Acquire);
+    if raw != 0 {
+        // the only non zero value stored is a valid hook function pointer
+        let hook: Hook = unsafe { core::mem::transmute::<usize, Hook>(raw) };
+        hook(kind, addr, value);
+    }
+}
@@ -0,0 +1,37 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! Tests of the byte order of the big-endian registers stored in the mocked address space
+
+#![cfg(feature = "mock")]
+
+use ruspiro_mmio_register::*;
+
+define_mmio_register!(
+    BE16<ReadWriteBE<u16>@(0x3F30_0000)>,
+    BE32<ReadWriteBE<u32>@(0x3F30_0010)>,
+    BE64<ReadWriteBE<u64>@(0x3F30_0020)> {
+        BYTE1 OFFSET(8) BITS(8)
+    }
+);
+
+#[test]
+fn big_endian_registers_are_stored_byte_swapped() {
+    mock::install_region(0x3F30_0000, 0x30);
+
+    BE16::Register.set(0x1234);
+    assert_eq!(mock::peek::<u16>(0x3F30_0000), 0x3412);
+    assert_eq!(BE16::Register.get(), 0x1234);
+
+    BE32::Register.set(0x1234_5678);
+    assert_eq!(mock::peek::<u32>(0x3F30_0010), 0x7856_3412);
+    assert_eq!(BE32::Register.get(), 0x1234_5678);
+
+    BE64::Register.set(0x0102_0304_0506_0708);
+    assert_eq!(mock::peek::<u64>(0x3F30_0020), 0x0807_0605_0403_0201);
+    assert_eq!(BE64::Register.modify(BE64::BYTE1, 0xAA), 0x0102_0304_0506_AA08);
+}
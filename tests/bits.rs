@@ -0,0 +1,87 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! Tests of the updates of single bits and of the bits of register fields against the mocked address space
+
+#![cfg(feature = "mock")]
+
+use ruspiro_mmio_register::*;
+
+define_mmio_register!(
+    LEVELS<ReadWrite<u32>@(0x3F7C_0000)> {
+        FLAG OFFSET(0),
+        LEVEL OFFSET(4) BITS(3)
+    },
+    SCRATCH<ReadWrite<u32>@(0x3F7C_0010)>,
+    OFFSETS<ReadWrite<u32>@(0x3F7C_0020)> {
+        TRIM OFFSET(4) BITS(4)
+    },
+    CHANNELS<ReadOnly<u32>@(0x3F7C_0030)> {
+        ACTIVE OFFSET(4) BITS(3)
+    }
+);
+
+#[test]
+fn toggle_inverts_the_bits_of_the_field_only() {
+    mock::install_region(0x3F7C_0000, 0x4);
+    mock::poke::<u32>(0x3F7C_0000, 0xF000_0050);
+
+    assert_eq!(LEVELS::Register.toggle(LEVELS::FLAG), 0xF000_0051);
+    assert_eq!(mock::peek::<u32>(0x3F7C_0000), 0xF000_0051);
+    assert_eq!(LEVELS::Register.toggle(LEVELS::LEVEL), 0xF000_0021);
+    assert_eq!(mock::peek::<u32>(0x3F7C_0000), 0xF000_0021);
+
+    // toggling twice restores the previous contents
+    LEVELS::Register.toggle(LEVELS::FLAG);
+    LEVELS::Register.toggle(LEVELS::LEVEL);
+    assert_eq!(mock::peek::<u32>(0x3F7C_0000), 0xF000_0050);
+}
+
+#[test]
+fn single_bits_at_both_ends_of_the_register() {
+    mock::install_region(0x3F7C_0010, 0x4);
+    mock::poke::<u32>(0x3F7C_0010, 0x0F0F_0F0E);
+
+    assert!(!SCRATCH::Register.get_bit(0));
+    assert!(!SCRATCH::Register.get_bit(u32::BITS - 1));
+    assert_eq!(SCRATCH::Register.set_bit(0), 0x0F0F_0F0F);
+    assert_eq!(SCRATCH::Register.set_bit(u32::BITS - 1), 0x8F0F_0F0F);
+    assert!(SCRATCH::Register.get_bit(0));
+    assert!(SCRATCH::Register.get_bit(u32::BITS - 1));
+
+    assert_eq!(SCRATCH::Register.clear_bit(u32::BITS - 1), 0x0F0F_0F0F);
+    assert_eq!(SCRATCH::Register.clear_bit(0), 0x0F0F_0F0E);
+    assert_eq!(mock::peek::<u32>(0x3F7C_0010), 0x0F0F_0F0E);
+}
+
+#[test]
+fn signed_values_at_the_bottom_of_the_field_range() {
+    mock::install_region(0x3F7C_0020, 0x4);
+    mock::poke::<u32>(0x3F7C_0020, 0xFFFF_FF0F);
+
+    OFFSETS::Register.write_signed(OFFSETS::TRIM, -1);
+    assert_eq!(mock::peek::<u32>(0x3F7C_0020), 0xF0);
+    assert_eq!(OFFSETS::Register.read_signed(OFFSETS::TRIM), -1);
+    OFFSETS::Register.write_signed(OFFSETS::TRIM, -8);
+    assert_eq!(mock::peek::<u32>(0x3F7C_0020), 0x80);
+    assert_eq!(OFFSETS::Register.read_signed(OFFSETS::TRIM), -8);
+
+    // a field without any bit provides 0 instead of shifting the value by its whole width
+    assert_eq!(OFFSETS::Register.read_signed(RegisterField::<u32>::new(0, 4)), 0);
+}
+
+#[test]
+fn count_the_bits_of_a_field_only() {
+    mock::install_region(0x3F7C_0030, 0x4);
+    mock::poke::<u32>(0x3F7C_0030, 0xFFFF_FF5F);
+
+    assert_eq!(CHANNELS::Register.field_count_ones(CHANNELS::ACTIVE), 2);
+    assert_eq!(CHANNELS::Register.field_count_zeros(CHANNELS::ACTIVE), 1);
+    mock::poke::<u32>(0x3F7C_0030, 0);
+    assert_eq!(CHANNELS::Register.field_count_ones(CHANNELS::ACTIVE), 0);
+    assert_eq!(CHANNELS::Register.field_count_zeros(CHANNELS::ACTIVE), 3);
+}
@@ -0,0 +1,39 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! Tests of the debug output of the registers against the mocked address space
+
+#![cfg(feature = "mock")]
+
+use ruspiro_mmio_register::*;
+
+define_mmio_peripheral!(
+    UART0 @ 0x3F70_0000 {
+        DR @ 0x00 <ReadWrite<u32>> {
+            DATA OFFSET(0) BITS(8)
+        },
+        MIS @ 0x04 <ReadOnly<u32>> {
+            RXMIS OFFSET(4) RC,
+            TXMIS OFFSET(5) RC
+        }
+    }
+);
+
+#[test]
+fn debug_output_does_not_read_registers_clearing_on_read() {
+    mock::install_region(0x3F70_0000, 0x8);
+    mock::poke::<u32>(0x3F70_0004, 0b11_0000);
+
+    assert_eq!(format!("{:?}", UART0::MIS::Register), "MIS(<read clears>)");
+    let dump = format!("{:?}", dump_all!(UART0));
+    assert!(dump.contains("MIS(<read clears>)"), "{}", dump);
+    assert_eq!(mock::reads(0x3F70_0004), 0);
+
+    // the contents are still printed if read explicitly
+    assert_eq!(format!("{:?}", UART0::MIS::Register.read_all()), "MIS { RXMIS: 0b1, TXMIS: 0b1 }");
+    assert_eq!(mock::reads(0x3F70_0004), 1);
+}
@@ -0,0 +1,33 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! Tests of the register fields defined with modifiers against the mocked address space
+
+#![cfg(feature = "mock")]
+
+use ruspiro_mmio_register::*;
+
+define_mmio_register!(
+    STATUS<ReadWrite<u32>@(0x3F40_0000)> {
+        DONE OFFSET(0) W1C,
+        ERROR OFFSET(1) W1C,
+        OVERRUN OFFSET(4) BITS(2) W1C
+    }
+);
+
+#[test]
+fn clear_writes_the_bits_of_the_write_one_to_clear_field_only() {
+    mock::install_region(0x3F40_0000, 0x4);
+
+    mock::poke::<u32>(0x3F40_0000, 0b11_0011);
+    STATUS::Register.clear(STATUS::ERROR);
+    // only the bit of the field cleared is written with 1
+    assert_eq!(mock::peek::<u32>(0x3F40_0000), 0b10);
+
+    STATUS::Register.clear(STATUS::OVERRUN);
+    assert_eq!(mock::peek::<u32>(0x3F40_0000), 0b11_0000);
+}
@@ -0,0 +1,146 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! Tests of the register updates with the field values of a register against the mocked address space
+
+#![cfg(feature = "mock")]
+
+use ruspiro_mmio_register::*;
+
+define_mmio_register!(
+    FIELDS<ReadWrite<u32>@(0x3F73_0000)> {
+        ENABLE OFFSET(0),
+        MODE OFFSET(4) BITS(2) [
+            SLOW = 0b01,
+            FAST = 0b10
+        ],
+        DIVIDER OFFSET(8) BITS(8)
+    },
+    CTRL<ReadWrite<u32>@(0x3F50_0000)> {
+        ENABLE OFFSET(0),
+        BAZ OFFSET(4) BITS(2) [
+            VAL1 = 0b01
+        ],
+        BAL OFFSET(8) BITS(2) [
+            OFF = 0b00,
+            VAL2 = 0b10
+        ]
+    },
+    SETUP<WriteOnly<u32>@(0x3F64_0000)> {
+        ENABLE OFFSET(0),
+        MODE OFFSET(4) BITS(2) [
+            FAST = 0b10
+        ],
+        DIVIDER OFFSET(8) BITS(8)
+    },
+    ENGINE<ReadOnly<u32>@(0x3F7D_0000)> {
+        STATE OFFSET(4) BITS(2)
+    }
+);
+
+/// The state of the engine as a driver would represent it
+#[derive(Debug, PartialEq)]
+enum EngineState {
+    Idle,
+    Busy,
+    Failed(u32),
+}
+
+impl From<u32> for EngineState {
+    fn from(raw: u32) -> Self {
+        match raw {
+            0 => EngineState::Idle,
+            1 => EngineState::Busy,
+            code => EngineState::Failed(code),
+        }
+    }
+}
+
+#[test]
+fn combined_field_values_update_the_bits_of_their_fields_only() {
+    mock::install_region(0x3F50_0000, 0x4);
+
+    mock::poke::<u32>(0x3F50_0000, 0b11_0011_0001);
+    CTRL::Register.modify_value(CTRL::BAZ::VAL1 | CTRL::BAL::VAL2);
+    // the enable bit outside of the fields updated survives
+    assert_eq!(mock::peek::<u32>(0x3F50_0000), 0b10_0001_0001);
+
+    // update_value does the same, while write_value sets all bits outside of the fields to 0
+    mock::poke::<u32>(0x3F50_0000, 0b11_0011_0001);
+    CTRL::Register.update_value(CTRL::BAZ::VAL1 | CTRL::BAL::VAL2);
+    assert_eq!(mock::peek::<u32>(0x3F50_0000), 0b10_0001_0001);
+    CTRL::Register.write_value(CTRL::BAZ::VAL1 | CTRL::BAL::VAL2);
+    assert_eq!(mock::peek::<u32>(0x3F50_0000), 0b10_0001_0000);
+
+    // save two fields with a single read and restore them later
+    mock::poke::<u32>(0x3F50_0000, 0b01_0010_0000);
+    let saved = CTRL::Register.read_values(&[CTRL::BAZ, CTRL::BAL]);
+    assert_eq!(saved.mask(), 0b11_0011_0000);
+    mock::poke::<u32>(0x3F50_0000, 0b11_0011_0001);
+    CTRL::Register.modify_value(saved);
+    assert_eq!(mock::peek::<u32>(0x3F50_0000), 0b01_0010_0001);
+
+    // update a field and restore its previous contents when leaving the nested configuration
+    let previous = CTRL::Register.replace_value(CTRL::BAL::VAL2);
+    assert_eq!(mock::peek::<u32>(0x3F50_0000), 0b10_0010_0001);
+    CTRL::Register.modify_value(previous);
+    assert_eq!(mock::peek::<u32>(0x3F50_0000), 0b01_0010_0001);
+
+    // a field combined with its zero encoding is cleared, not left untouched
+    mock::poke::<u32>(0x3F50_0000, 0b11_0011_0001);
+    CTRL::Register.modify_value(CTRL::BAZ::VAL1 | CTRL::BAL::OFF);
+    assert_eq!(mock::peek::<u32>(0x3F50_0000), 0b00_0001_0001);
+    mock::poke::<u32>(0x3F50_0000, 0b11_0011_0001);
+    (*CTRL::Register).modify_value(CTRL::BAL::OFF.value() | CTRL::BAZ::VAL1.value());
+    assert_eq!(mock::peek::<u32>(0x3F50_0000), 0b00_0001_0001);
+}
+
+#[test]
+fn write_values_configures_a_write_only_register_with_a_single_write() {
+    mock::install_region(0x3F64_0000, 0x4);
+    mock::poke::<u32>(0x3F64_0000, 0xFFFF_FFFF);
+
+    SETUP::Register.write_values(&[
+        SETUP::ENABLE::with_value(1),
+        SETUP::MODE::FAST,
+        SETUP::DIVIDER::with_value(0x20),
+    ]);
+    // the bits not covered by any of the field values are written as 0
+    assert_eq!(mock::writes(0x3F64_0000), 1);
+    assert_eq!(mock::peek::<u32>(0x3F64_0000), 0x2021);
+}
+
+#[test]
+fn modify_fields_updates_the_fields_given_only() {
+    mock::install_region(0x3F73_0000, 0x4);
+    mock::poke::<u32>(0x3F73_0000, 0xF000_0031);
+
+    let new_val = FIELDS::Register.modify_fields(&[FIELDS::MODE::FAST, FIELDS::DIVIDER::with_value(0x20)]);
+    assert_eq!(new_val, 0xF000_2021);
+    assert_eq!(mock::peek::<u32>(0x3F73_0000), 0xF000_2021);
+    assert_eq!(mock::reads(0x3F73_0000), 1);
+    assert_eq!(mock::writes(0x3F73_0000), 1);
+
+    // the last value of overlapping fields wins
+    FIELDS::Register.modify_fields(&[FIELDS::MODE::FAST, FIELDS::MODE::SLOW]);
+    assert_eq!(mock::peek::<u32>(0x3F73_0000), 0xF000_2011);
+}
+
+#[test]
+fn read_into_converts_into_a_driver_type() {
+    mock::install_region(0x3F7D_0000, 0x4);
+
+    mock::poke::<u32>(0x3F7D_0000, 0xF01F);
+    assert_eq!(ENGINE::Register.read_field_into::<EngineState>(ENGINE::STATE), EngineState::Busy);
+    mock::poke::<u32>(0x3F7D_0000, 0xF03F);
+    assert_eq!(ENGINE::Register.read_field_into::<EngineState>(ENGINE::STATE), EngineState::Failed(3));
+
+    // the raw value of the whole register is converted
+    mock::poke::<u32>(0x3F7D_0000, 0);
+    let state: EngineState = ENGINE::Register.read_into();
+    assert_eq!(state, EngineState::Idle);
+}
@@ -0,0 +1,52 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! Tests of registers defining a field for each of their bits, like the GPIO registers setting, clearing or reading
+//! one pin per bit. Those need to be defined without raising the recursion limit of the crate using the macro.
+
+#![cfg(feature = "mock")]
+
+use ruspiro_mmio_register::*;
+
+define_mmio_register!(
+    GPSET0<ReadWrite<u32>@(0x3F7A_0000)> {
+        F0 OFFSET(0), F1 OFFSET(1), F2 OFFSET(2), F3 OFFSET(3), F4 OFFSET(4), F5 OFFSET(5), F6 OFFSET(6),
+        F7 OFFSET(7), F8 OFFSET(8), F9 OFFSET(9), F10 OFFSET(10), F11 OFFSET(11), F12 OFFSET(12), F13 OFFSET(13),
+        F14 OFFSET(14), F15 OFFSET(15), F16 OFFSET(16), F17 OFFSET(17), F18 OFFSET(18), F19 OFFSET(19),
+        F20 OFFSET(20), F21 OFFSET(21), F22 OFFSET(22), F23 OFFSET(23), F24 OFFSET(24), F25 OFFSET(25),
+        F26 OFFSET(26), F27 OFFSET(27), F28 OFFSET(28), F29 OFFSET(29), F30 OFFSET(30), F31 OFFSET(31)
+    },
+    GPEDS0<ReadWrite<u32>@(0x3F7A_0010)> {
+        /// Event detected on pin 0
+        F0 OFFSET(0) W1C, F1 OFFSET(1) W1C, F2 OFFSET(2) W1C, F3 OFFSET(3) W1C, F4 OFFSET(4) W1C,
+        F5 OFFSET(5) W1C, F6 OFFSET(6) W1C, F7 OFFSET(7) W1C, F8 OFFSET(8) W1C, F9 OFFSET(9) W1C,
+        F10 OFFSET(10) W1C, F11 OFFSET(11) W1C, F12 OFFSET(12) W1C, F13 OFFSET(13) W1C, F14 OFFSET(14) W1C,
+        F15 OFFSET(15) W1C, F16 OFFSET(16) W1C, F17 OFFSET(17) W1C, F18 OFFSET(18) W1C, F19 OFFSET(19) W1C,
+        F20 OFFSET(20) W1C, F21 OFFSET(21) W1C, F22 OFFSET(22) W1C, F23 OFFSET(23) W1C, F24 OFFSET(24) W1C,
+        F25 OFFSET(25) W1C, F26 OFFSET(26) W1C, F27 OFFSET(27) W1C, F28 OFFSET(28) W1C, F29 OFFSET(29) W1C,
+        F30 OFFSET(30) W1C, F31 OFFSET(31) W1C
+    }
+);
+
+#[test]
+fn register_with_32_fields() {
+    mock::install_region(0x3F7A_0000, 0x4);
+
+    GPSET0::Register.write_value(GPSET0::F31::with_value(1) | GPSET0::F0::with_value(1));
+    assert_eq!(mock::peek::<u32>(0x3F7A_0000), 0x8000_0001);
+    assert_eq!(GPSET0::Register.read(GPSET0::F17), 0);
+    assert_eq!(GPSET0::FIELDS.len(), 32);
+}
+
+#[test]
+fn register_with_32_write_one_to_clear_fields() {
+    mock::install_region(0x3F7A_0010, 0x4);
+
+    mock::poke::<u32>(0x3F7A_0010, 0xFFFF_FFFF);
+    GPEDS0::Register.clear(GPEDS0::F31);
+    assert_eq!(mock::peek::<u32>(0x3F7A_0010), 0x8000_0000);
+}
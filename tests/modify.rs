@@ -0,0 +1,171 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! Tests of the read-modify-write of registers against the mocked address space, also while several threads update
+//! the same register
+
+#![cfg(feature = "mock")]
+
+use ruspiro_mmio_register::*;
+use std::sync::{Arc, Barrier};
+
+define_mmio_register!(
+    COUNTER<ReadWrite<u32>@(0x3F71_0000)> {
+        ENABLE OFFSET(0),
+        COUNT OFFSET(8) BITS(16)
+    },
+    ATOMIC_COUNTER<AtomicReadWrite<u32>@(0x3F71_0010)> {
+        ENABLE OFFSET(0),
+        COUNT OFFSET(8) BITS(16)
+    },
+    CONFIG<ReadWrite<u32>@(0x3F71_0020)> {
+        ENABLE OFFSET(0),
+        MODE OFFSET(4) BITS(2),
+        RSVD OFFSET(8) BITS(8) RESERVED
+    },
+    GUARDED<ReadWrite<u32>@(0x3F71_0030)> {
+        LOW OFFSET(4) BITS(4),
+        RSVD OFFSET(8) BITS(8) RESERVED,
+        HIGH OFFSET(16) BITS(4)
+    },
+    CFG<ReadWrite<u32>@(0x3F58_0000)> {
+        ENABLE OFFSET(0),
+        MODE OFFSET(4) BITS(2),
+        DIVIDER OFFSET(8) BITS(8)
+    }
+);
+
+const THREADS: u32 = 4;
+const INCREMENTS: u32 = 1000;
+
+/// Increment the count of the register from several threads at the same time
+fn count_concurrently(increment: fn()) {
+    let start = Arc::new(Barrier::new(THREADS as usize));
+    let threads: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let start = Arc::clone(&start);
+            std::thread::spawn(move || {
+                start.wait();
+                for _ in 0..INCREMENTS {
+                    increment();
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+}
+
+/// Update the register while another thread holds the critical section and changes the reserved bits of the register
+/// within it. The update needs to wait for the critical section, so it reads the reserved bits changed.
+#[cfg(feature = "critical-section")]
+fn update_in_critical_section(update: fn()) {
+    mock::install_region(0x3F71_0020, 0x4);
+
+    let entered = Arc::new(Barrier::new(2));
+    let holder = {
+        let entered = Arc::clone(&entered);
+        std::thread::spawn(move || {
+            critical_section::with(|_| {
+                entered.wait();
+                std::thread::sleep(std::time::Duration::from_millis(50));
+                mock::poke::<u32>(0x3F71_0020, 0xA5 << 8);
+            })
+        })
+    };
+    entered.wait();
+    update();
+    holder.join().unwrap();
+
+    assert_eq!(mock::peek::<u32>(0x3F71_0020), (0xA5 << 8) | 0b10_0001);
+}
+
+#[test]
+fn modify_all_reads_and_writes_the_register_once() {
+    mock::install_region(0x3F58_0000, 0x4);
+
+    CFG::Register.modify_all(|values| values.with_enable(1).with_mode(0b10).with_divider(0x20));
+    assert_eq!(mock::reads(0x3F58_0000), 1);
+    assert_eq!(mock::writes(0x3F58_0000), 1);
+    assert_eq!(mock::peek::<u32>(0x3F58_0000), 0x2021);
+
+    // the register is not written again if the field already contains the value
+    assert!(!CFG::Register.modify_if_changed(CFG::MODE, 0b10));
+    assert_eq!(mock::writes(0x3F58_0000), 1);
+    assert!(CFG::Register.modify_if_changed(CFG::MODE, 0b11));
+    assert_eq!(mock::writes(0x3F58_0000), 2);
+}
+
+#[cfg(feature = "critical-section")]
+#[test]
+fn modify_all_in_critical_section_loses_no_update() {
+    mock::install_region(0x3F71_0000, 0x4);
+    COUNTER::Register.modify(COUNTER::ENABLE, 1);
+
+    count_concurrently(|| {
+        COUNTER::Register.modify_all(|values| {
+            // give the other threads the chance to update the register in between the read and the write
+            std::thread::yield_now();
+            values.with_count(values.count() + 1)
+        });
+    });
+    assert_eq!(COUNTER::Register.read(COUNTER::COUNT), THREADS * INCREMENTS);
+    assert_eq!(COUNTER::Register.read(COUNTER::ENABLE), 1);
+}
+
+#[cfg(feature = "critical-section")]
+#[test]
+fn modify_all_waits_for_critical_section() {
+    update_in_critical_section(|| {
+        CONFIG::Register.modify_all(|values| values.with_enable(1).with_mode(0b10));
+    });
+}
+
+#[cfg(feature = "critical-section")]
+#[test]
+fn write_config_waits_for_critical_section() {
+    update_in_critical_section(|| {
+        CONFIG::Register.write_config(CONFIG::ENABLE::with_value(1) | CONFIG::MODE::with_value(0b10));
+    });
+}
+
+#[cfg(feature = "critical-section")]
+#[test]
+fn built_modify_waits_for_critical_section() {
+    update_in_critical_section(|| {
+        CONFIG::Register.build().enable(1).mode(0b10).modify();
+    });
+}
+
+#[test]
+fn modify_all_of_atomic_register_loses_no_update() {
+    mock::install_region(0x3F71_0010, 0x4);
+    ATOMIC_COUNTER::Register.modify(ATOMIC_COUNTER::ENABLE, 1);
+
+    count_concurrently(|| {
+        ATOMIC_COUNTER::Register.modify_all(|values| values.with_count(values.count() + 1));
+    });
+    assert_eq!(ATOMIC_COUNTER::Register.read(ATOMIC_COUNTER::COUNT), THREADS * INCREMENTS);
+    assert_eq!(ATOMIC_COUNTER::Register.read(ATOMIC_COUNTER::ENABLE), 1);
+}
+
+#[test]
+fn modify_safe_next_to_reserved_bits_keeps_them() {
+    mock::install_region(0x3F71_0030, 0x4);
+    mock::poke::<u32>(0x3F71_0030, 0x0000_A500);
+
+    assert_eq!(GUARDED::Register.modify_safe(GUARDED::LOW, 0xF), 0x0000_A5F0);
+    assert_eq!(GUARDED::Register.modify_safe(GUARDED::HIGH, 0xF), 0x000F_A5F0);
+    assert_eq!(mock::peek::<u32>(0x3F71_0030), 0x000F_A5F0);
+}
+
+#[test]
+#[should_panic(expected = "register field covers reserved bits")]
+fn modify_safe_rejects_a_field_covering_reserved_bits() {
+    GUARDED::Register.modify_safe(RegisterField::<u32>::new(0xFF, 4), 0);
+}
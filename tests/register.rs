@@ -0,0 +1,59 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! Tests of the accesses to the whole register against the mocked address space
+
+#![cfg(feature = "mock")]
+
+use ruspiro_mmio_register::*;
+
+define_mmio_register!(
+    SCRATCH<ReadWrite<u32>@(0x3F60_0000)>,
+    FIFO<ReadWrite<u32>@(0x3F60_0010)>
+);
+
+#[test]
+fn walking_ones_and_zeros() {
+    mock::install_region(0x3F60_0000, 0x4);
+
+    SCRATCH::Register.set_all();
+    assert_eq!(mock::peek::<u32>(0x3F60_0000), 0xFFFF_FFFF);
+    SCRATCH::Register.clear_all();
+    assert_eq!(mock::peek::<u32>(0x3F60_0000), 0);
+
+    // walking ones and their inverted walking zeros
+    for bit in 0..32 {
+        SCRATCH::Register.set(1 << bit);
+        assert_eq!(SCRATCH::Register.get(), 1 << bit);
+        assert_eq!(SCRATCH::Register.invert(), !(1 << bit));
+        assert_eq!(mock::peek::<u32>(0x3F60_0000), !(1 << bit));
+    }
+}
+
+#[test]
+fn try_new_rejects_null_and_misaligned_addresses() {
+    assert!(ReadWrite::<u32>::try_new(0).is_none());
+    assert!(ReadWrite::<u32>::try_new(0x3F60_0002).is_none());
+    assert!(ReadWrite::<u16>::try_new(0x3F60_0001).is_none());
+    assert_eq!(ReadWrite::<u32>::try_new(0x3F60_0000).map(|register| register.address()), Some(0x3F60_0000));
+}
+
+#[test]
+fn fifo_accesses_stay_at_the_same_register() {
+    mock::install_region(0x3F60_0010, 0x8);
+
+    FIFO::Register.write_repeated(0xA5A5_5A5A, 4);
+    assert_eq!(mock::writes(0x3F60_0010), 4);
+    assert_eq!(mock::writes(0x3F60_0014), 0);
+    assert_eq!(mock::peek::<u32>(0x3F60_0010), 0xA5A5_5A5A);
+
+    let mut data = [0u32; 3];
+    FIFO::Register.read_slice(&mut data);
+    assert_eq!(mock::reads(0x3F60_0010), 3);
+    assert_eq!(mock::reads(0x3F60_0014), 0);
+    assert_eq!(data, [0xA5A5_5A5A; 3]);
+}
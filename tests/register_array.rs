@@ -0,0 +1,38 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! Tests of the accesses to the registers of a register array against the mocked address space
+
+#![cfg(feature = "mock")]
+
+use ruspiro_mmio_register::*;
+
+define_mmio_register_array!(
+    CHANNEL<ReadWrite<u32>@(0x3F7E_0000, 0x100, 4)> {
+        ACTIVE OFFSET(0),
+        PRIORITY OFFSET(16) BITS(4)
+    }
+);
+
+#[test]
+fn iter_accesses_each_register_of_the_array() {
+    mock::install_region(0x3F7E_0000, 0x400);
+
+    let channels = CHANNEL::Register.iter();
+    assert_eq!(channels.len(), CHANNEL::COUNT);
+    for (index, channel) in channels.enumerate() {
+        channel.write(CHANNEL::PRIORITY, index as u32 + 1);
+    }
+
+    for index in 0..CHANNEL::COUNT {
+        let address = 0x3F7E_0000 + index * 0x100;
+        assert_eq!(mock::peek::<u32>(address), (index as u32 + 1) << 16);
+        assert_eq!(mock::writes(address), 1);
+        assert_eq!(CHANNEL::Register.at(index).read(CHANNEL::PRIORITY), index as u32 + 1);
+    }
+    assert_eq!(CHANNEL::Register.iter().next_back().map(|channel| channel.address()), Some(0x3F7E_0300));
+}
@@ -0,0 +1,38 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! Tests of the register snapshots read from the mocked address space
+
+#![cfg(feature = "mock")]
+
+use ruspiro_mmio_register::*;
+
+define_mmio_register!(
+    CTRL<ReadWrite<u32>@(0x3F72_0000)> {
+        ENABLE OFFSET(0),
+        MODE OFFSET(4) BITS(2),
+        RSVD OFFSET(8) BITS(4) RESERVED
+    }
+);
+
+#[test]
+fn changed_fields_ignores_the_bits_not_compared() {
+    mock::install_region(0x3F72_0000, 0x4);
+
+    mock::poke::<u32>(0x3F72_0000, 0b0001_0001);
+    let before = CTRL::Register.read_all();
+    // only undeclared bits and the bits of the reserved field change
+    mock::poke::<u32>(0x3F72_0000, 0xF000_0F01 | 0b0001_0000);
+    let after = CTRL::Register.read_all();
+    assert_eq!(before, after);
+    assert_eq!(before.changed_fields(&after), 0);
+
+    mock::poke::<u32>(0x3F72_0000, 0xF000_0F00 | 0b0010_0000);
+    let after = CTRL::Register.read_all();
+    assert_ne!(before, after);
+    assert_eq!(before.changed_fields(&after), 0b0011_0001);
+}
@@ -0,0 +1,44 @@
+/***********************************************************************************************************************
+ * Copyright (c) 2020 by the authors
+ *
+ * Author: André Borrmann <pspwizard@gmx.de>
+ * License: Apache License 2.0 / MIT
+ **********************************************************************************************************************/
+
+//! Tests of waiting for register fields changed by another thread in the mocked address space
+
+#![cfg(feature = "mock")]
+
+use ruspiro_mmio_register::*;
+
+define_mmio_register!(
+    FIFO<ReadOnly<u32>@(0x3F68_0000)> {
+        LEVEL OFFSET(8) BITS(4)
+    }
+);
+
+#[test]
+fn wait_for_a_ramping_fill_level() {
+    mock::install_region(0x3F68_0000, 0x4);
+    assert_eq!(FIFO::Register.wait_until_ge_timeout(FIFO::LEVEL, 8, 100), Err(()));
+
+    // the ramp holds the peak until the threshold has been seen and stays at 0 at its end, so the waits do not
+    // depend on the thread timing
+    let (peak_seen, peak_seen_rx) = std::sync::mpsc::channel();
+    let ramp = std::thread::spawn(move || {
+        for level in 0..=15 {
+            mock::poke::<u32>(0x3F68_0000, level << 8);
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        peak_seen_rx.recv().unwrap();
+        for level in (0..15).rev() {
+            mock::poke::<u32>(0x3F68_0000, level << 8);
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+    });
+    FIFO::Register.wait_until_ge(FIFO::LEVEL, 12);
+    peak_seen.send(()).unwrap();
+    FIFO::Register.wait_until_le(FIFO::LEVEL, 2);
+    ramp.join().unwrap();
+    assert_eq!(FIFO::Register.wait_until_le_timeout(FIFO::LEVEL, 0, 1), Ok(()));
+}